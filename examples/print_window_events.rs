@@ -12,11 +12,11 @@ fn print_event(event: MaybeWindowAvailable) {
             }
 
             match event {
-                Event::Moved => {
-                    println!("\tWindow position: {:?}", window.position());
+                Event::Moved { bounds } => {
+                    println!("\tWindow position: {:?}", bounds.position());
                 }
-                Event::Resized => {
-                    println!("\tWindow size: {:?}", window.size());
+                Event::Resized { bounds } => {
+                    println!("\tWindow size: {:?}", bounds.size());
                 }
                 _ => {}
             }
@@ -24,6 +24,10 @@ fn print_event(event: MaybeWindowAvailable) {
         MaybeWindowAvailable::NotAvailable { event } => {
             println!("\n{event:?}");
         }
+        MaybeWindowAvailable::InitialState { window, event } => {
+            println!("\n{event:?} (initial state)");
+            println!("\tWindow title: {:?}", window.title());
+        }
     };
 }
 
@@ -34,9 +38,14 @@ async fn main() {
         .expect("Please give me the env `PID` of application that has window.");
 
     let (event_tx, mut event_rx) = tokio::sync::mpsc::unbounded_channel();
-    let event_filter = window_observer::EventFilter::all();
+    let event_filter = window_observer::Capabilities::current().events;
 
-    let _window_observer = WindowObserver::start(pid, event_tx, event_filter)
+    let _window_observer = WindowObserver::builder()
+        .pid(pid)
+        .filter(event_filter)
+        .channel(event_tx)
+        .initial_snapshot(true)
+        .start()
         .await
         .unwrap();
 
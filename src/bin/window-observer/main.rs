@@ -0,0 +1,470 @@
+//! The command-line tool for observing the windows of an application.
+//!
+//! It is built with the `cli` feature, for example by `cargo run --features cli -- watch --pid 123`.
+
+mod output;
+
+use std::{
+    error::Error,
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+use clap::{Args, Parser, Subcommand};
+use serde::{Deserialize, Serialize};
+use window_observer::{
+    Capabilities, Event, EventFilter, EventResult, EventRx, EventTx, MaybeWindowAvailable,
+    ObserverEvent, ProcessMatcher, SupervisedWindowObserver, WindowObserver,
+    wire::{EventRecord, WindowSnapshot},
+};
+
+use output::{Format, ListedWindow};
+
+/// Observes the windows of an application.
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Streams the events of the windows of an application until interrupted.
+    Watch {
+        #[command(flatten)]
+        target: TargetArgs,
+        #[command(flatten)]
+        observe: ObserveArgs,
+        /// The format of the output.
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+    },
+    /// Lists the windows on the screen with their bounds.
+    List {
+        /// Lists only the windows of the process.
+        #[arg(long, conflicts_with = "name")]
+        pid: Option<u32>,
+        /// Lists only the windows of the application, such as `TextEdit` or `notepad.exe`.
+        #[arg(long)]
+        name: Option<String>,
+        /// The format of the output.
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+    },
+    /// Prints the current state of the windows of an application.
+    Snapshot {
+        #[command(flatten)]
+        target: TargetArgs,
+        /// The format of the output.
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+    },
+    /// Writes the events of the windows of an application to a file until interrupted.
+    Record {
+        #[command(flatten)]
+        target: TargetArgs,
+        #[command(flatten)]
+        observe: ObserveArgs,
+        /// The file to write the recording to. Each line is a JSON object.
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+    /// Prints the events of a recording written by `record`.
+    Replay {
+        /// The file of the recording.
+        input: PathBuf,
+        /// Replays with the original timing scaled by the factor, such as `2` for twice as fast.
+        /// Without it, the events are printed at once.
+        #[arg(long)]
+        speed: Option<f64>,
+        /// The format of the output.
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+    },
+}
+
+/// The application to observe.
+#[derive(Args)]
+#[group(required = true, multiple = false)]
+struct TargetArgs {
+    /// The ID of the process.
+    #[arg(long)]
+    pid: Option<u32>,
+    /// The name of the application, such as `TextEdit` or `notepad.exe`.
+    /// With `watch` and `record`, the application is observed again when it restarts.
+    #[arg(long)]
+    name: Option<String>,
+}
+
+impl TargetArgs {
+    /// Returns the ID of the process, finding a running process by the name if needed.
+    fn find_pid(&self) -> Result<u32, Box<dyn Error>> {
+        match self.pid {
+            Some(pid) => Ok(pid),
+            None => {
+                let name = self.name.as_deref().unwrap_or_default();
+
+                ProcessMatcher::name(name).find().ok_or_else(|| {
+                    format!("No window of the application `{name}` was found").into()
+                })
+            }
+        }
+    }
+}
+
+#[derive(Args)]
+struct ObserveArgs {
+    /// The events to observe, such as `moved,resized`. See `EventFilter` for the names.
+    /// It defaults to all events that the platform supports.
+    #[arg(long)]
+    events: Option<EventFilter>,
+    /// Sends the current state of the windows first. It can't be used with `--name`.
+    #[arg(long, conflicts_with = "name")]
+    initial: bool,
+}
+
+impl ObserveArgs {
+    /// Returns the events to observe, which are the supported ones if they are not given.
+    fn events(&self, supervised: bool) -> EventFilter {
+        self.events.unwrap_or_else(|| {
+            let mut events = Capabilities::current().events;
+            // They are sent by the supervisor instead of the backend.
+            events.process_attached = supervised;
+            events.process_detached = supervised;
+            events
+        })
+    }
+}
+
+/// A line of a recording.
+#[derive(Serialize, Deserialize)]
+struct RecordedEvent {
+    /// The time since the recording started.
+    elapsed_ms: u64,
+    record: EventRecord,
+}
+
+/// An observer of a process or of the processes of an application.
+enum Observer {
+    Process(WindowObserver),
+    Supervised(SupervisedWindowObserver),
+}
+
+impl Observer {
+    async fn start(
+        target: &TargetArgs,
+        observe: &ObserveArgs,
+        event_tx: EventTx,
+    ) -> Result<Self, window_observer::Error> {
+        match (target.pid, &target.name) {
+            (None, Some(name)) => Ok(Self::Supervised(SupervisedWindowObserver::start(
+                ProcessMatcher::name(name),
+                event_tx,
+                observe.events(true),
+            )?)),
+            (pid, _) => Ok(Self::Process(
+                WindowObserver::builder()
+                    .pid(pid.unwrap_or_default())
+                    .filter(observe.events(false))
+                    .channel(event_tx)
+                    .initial_snapshot(observe.initial)
+                    .start()
+                    .await?,
+            )),
+        }
+    }
+
+    async fn stop(self) -> Result<(), Box<dyn Error>> {
+        match self {
+            Self::Process(observer) => observer.stop().await?,
+            Self::Supervised(observer) => tokio::task::spawn_blocking(|| observer.stop()).await?,
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns `true` if the observer can't keep working after the result.
+fn is_failure(result: &EventResult) -> bool {
+    result
+        .as_ref()
+        .is_err_and(|error| !error.kind().is_recoverable())
+}
+
+/// Returns whether the observer is degraded after the result, given whether it was before.
+fn is_degraded(result: &EventResult, degraded: bool) -> bool {
+    match result.as_ref().map(MaybeWindowAvailable::event) {
+        Ok(Event::Lifecycle {
+            event: ObserverEvent::Degraded { .. },
+        }) => true,
+        Ok(Event::Lifecycle {
+            event: ObserverEvent::Recovered,
+        }) => false,
+        _ => degraded,
+    }
+}
+
+/// Returns `true` if the observer is stopped by the result.
+///
+/// A supervised observer keeps waiting for the next process after the target has exited.
+fn is_end(result: &EventResult, supervised: bool) -> bool {
+    is_failure(result)
+        || !supervised
+            && matches!(
+                result.as_ref().map(MaybeWindowAvailable::event),
+                Ok(Event::Lifecycle {
+                    event: ObserverEvent::TargetExited { .. }
+                })
+            )
+}
+
+/// Handles the events until interrupted, until the target exits or until the observer fails,
+/// then stops the observer.
+///
+/// It fails if the observer has failed or has been degraded without recovering.
+async fn observe(
+    target: &TargetArgs,
+    observe: &ObserveArgs,
+    mut handle: impl FnMut(&EventResult) -> std::io::Result<()>,
+) -> Result<ExitCode, Box<dyn Error>> {
+    let (event_tx, mut event_rx): (EventTx, EventRx) = tokio::sync::mpsc::unbounded_channel();
+    let observer = Observer::start(target, observe, event_tx).await?;
+    let interrupted = tokio::signal::ctrl_c();
+    tokio::pin!(interrupted);
+    let supervised = matches!(observer, Observer::Supervised(_));
+    let mut failed = false;
+    let mut degraded = false;
+
+    loop {
+        tokio::select! {
+            _ = &mut interrupted => break,
+            result = event_rx.recv() => match result {
+                Some(result) => {
+                    handle(&result)?;
+                    failed = is_failure(&result);
+                    degraded = is_degraded(&result, degraded);
+                    if is_end(&result, supervised) {
+                        break;
+                    }
+                }
+                None => break,
+            },
+        }
+    }
+
+    observer.stop().await?;
+
+    // The events that were pending when the observer was stopped.
+    while let Ok(result) = event_rx.try_recv() {
+        handle(&result)?;
+        degraded = is_degraded(&result, degraded);
+    }
+
+    Ok(if failed || degraded {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+fn list(
+    pid: Option<u32>,
+    name: Option<String>,
+    format: Format,
+) -> Result<ExitCode, Box<dyn Error>> {
+    let matcher = name.map(ProcessMatcher::name);
+    let windows: Vec<_> = window_observer::window_getter::get_windows()?
+        .iter()
+        .map(ListedWindow::from)
+        .filter(|window| pid.is_none() || window.pid == pid)
+        .filter(|window| {
+            matcher.as_ref().is_none_or(|matcher| {
+                window
+                    .pid
+                    .zip(window.app.as_deref())
+                    .is_some_and(|(pid, app)| matcher.matches(pid, app))
+            })
+        })
+        .collect();
+
+    output::print_windows(&windows, format)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+async fn snapshot(target: &TargetArgs, format: Format) -> Result<ExitCode, Box<dyn Error>> {
+    let (event_tx, mut event_rx): (EventTx, EventRx) = tokio::sync::mpsc::unbounded_channel();
+    let mut event_filter = EventFilter::empty();
+    event_filter.created = true;
+
+    let observer = WindowObserver::builder()
+        .pid(target.find_pid()?)
+        .filter(event_filter)
+        .channel(event_tx)
+        .initial_snapshot(true)
+        .start()
+        .await?;
+    let mut windows = Vec::new();
+
+    while let Some(result) = event_rx.recv().await {
+        if is_failure(&result) {
+            output::print_record(&EventRecord::capture_result(&result), format)?;
+            observer.stop().await?;
+            return Ok(ExitCode::FAILURE);
+        }
+
+        match result {
+            Ok(MaybeWindowAvailable::InitialState { window, .. }) => {
+                windows.push(WindowSnapshot::capture(&window));
+            }
+            Ok(MaybeWindowAvailable::NotAvailable {
+                event: Event::InitialStateCompleted,
+            }) => break,
+            _ => {}
+        }
+    }
+
+    observer.stop().await?;
+    output::print_snapshots(&windows, format)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+async fn record(
+    target: &TargetArgs,
+    observe_args: &ObserveArgs,
+    path: &Path,
+) -> Result<ExitCode, Box<dyn Error>> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    let started_at = Instant::now();
+
+    observe(target, observe_args, |result| {
+        let event = RecordedEvent {
+            elapsed_ms: started_at.elapsed().as_millis() as u64,
+            record: EventRecord::capture_result(result),
+        };
+
+        serde_json::to_writer(&mut writer, &event)?;
+        // Each line is flushed so that the recording is usable even if the tool is killed.
+        writeln!(writer)?;
+        writer.flush()
+    })
+    .await
+}
+
+async fn replay(
+    path: &Path,
+    speed: Option<f64>,
+    format: Format,
+) -> Result<ExitCode, Box<dyn Error>> {
+    let started_at = Instant::now();
+    let mut failed = false;
+
+    for line in BufReader::new(File::open(path)?).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let event: RecordedEvent = serde_json::from_str(&line)?;
+
+        if let Some(speed) = speed.filter(|speed| *speed > 0.) {
+            let at = Duration::from_millis(event.elapsed_ms).div_f64(speed);
+            tokio::time::sleep(at.saturating_sub(started_at.elapsed())).await;
+        }
+
+        output::print_record(&event.record, format)?;
+
+        if let EventRecord::Error { error } = &event.record {
+            failed |= !error.kind.is_recoverable();
+        }
+    }
+
+    Ok(if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let result = match Cli::parse().command {
+        Command::Watch {
+            target,
+            observe: observe_args,
+            format,
+        } => {
+            observe(&target, &observe_args, |result| {
+                output::print_record(&EventRecord::capture_result(result), format)
+            })
+            .await
+        }
+        Command::List { pid, name, format } => list(pid, name, format),
+        Command::Snapshot { target, format } => snapshot(&target, format).await,
+        Command::Record {
+            target,
+            observe,
+            output,
+        } => record(&target, &observe, &output).await,
+        Command::Replay {
+            input,
+            speed,
+            format,
+        } => replay(&input, speed, format).await,
+    };
+
+    result.unwrap_or_else(|error| {
+        eprintln!("error: {error}");
+        ExitCode::FAILURE
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use window_observer::DegradedReason;
+
+    use super::*;
+
+    fn lifecycle(event: ObserverEvent) -> EventResult {
+        Ok(MaybeWindowAvailable::NotAvailable {
+            event: Event::Lifecycle { event },
+        })
+    }
+
+    fn degraded() -> EventResult {
+        lifecycle(ObserverEvent::Degraded {
+            reason: DegradedReason::BackendEnded,
+        })
+    }
+
+    /// Returns whether the observer is degraded after the results.
+    fn is_degraded_after(results: &[EventResult]) -> bool {
+        results
+            .iter()
+            .fold(false, |degraded, result| is_degraded(result, degraded))
+    }
+
+    #[test]
+    fn target_exit_ends_only_an_unsupervised_observer() {
+        let exited = lifecycle(ObserverEvent::TargetExited { pid: 1 });
+
+        assert!(is_end(&exited, false));
+        assert!(!is_end(&exited, true));
+        assert!(!is_end(&lifecycle(ObserverEvent::Started), false));
+        assert!(!is_end(&degraded(), false));
+    }
+
+    #[test]
+    fn degraded_lasts_until_recovered() {
+        let started = || lifecycle(ObserverEvent::Started);
+        let recovered = || lifecycle(ObserverEvent::Recovered);
+
+        assert!(!is_degraded_after(&[started()]));
+        assert!(is_degraded_after(&[degraded(), started()]));
+        assert!(!is_degraded_after(&[degraded(), recovered()]));
+        assert!(is_degraded_after(&[degraded(), recovered(), degraded()]));
+    }
+}
@@ -0,0 +1,142 @@
+//! Printing of the windows and the events.
+
+use std::io::{self, Write};
+
+use serde::Serialize;
+use window_observer::{
+    Rect,
+    wire::{EventRecord, WindowSnapshot},
+};
+
+/// The format of the output.
+#[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
+pub enum Format {
+    /// A line of text for each item.
+    #[default]
+    Pretty,
+    /// A JSON object for each event, or a JSON array of the windows.
+    Json,
+}
+
+/// A window listed by `list`.
+#[derive(Debug, Serialize)]
+pub struct ListedWindow {
+    pub id: u32,
+    pub pid: Option<u32>,
+    pub app: Option<String>,
+    pub title: Option<String>,
+    pub bounds: Option<Rect>,
+}
+
+impl From<&window_observer::window_getter::Window> for ListedWindow {
+    fn from(window: &window_observer::window_getter::Window) -> Self {
+        Self {
+            id: window.id().as_u32(),
+            pid: window.owner_pid().ok().map(|pid| pid as u32),
+            app: window.owner_name().ok().flatten(),
+            title: window.title().ok().flatten(),
+            bounds: window.bounds().ok().map(Rect::from),
+        }
+    }
+}
+
+fn describe_bounds(bounds: Option<Rect>) -> String {
+    match bounds {
+        Some(bounds) => format!(
+            "({}, {}) {}x{}",
+            bounds.x, bounds.y, bounds.width, bounds.height
+        ),
+        None => "(unknown bounds)".to_owned(),
+    }
+}
+
+fn describe_window(window: &WindowSnapshot) -> String {
+    let mut text = format!("{:?}", window.title.as_deref().unwrap_or_default());
+
+    if let Some(id) = window.id {
+        text += &format!(" id={id}");
+    }
+    if let Some(pid) = window.pid {
+        text += &format!(" pid={pid}");
+    }
+    text += &format!(" {}", describe_bounds(window.bounds));
+    if window.focused {
+        text += " focused";
+    }
+    if window.state.minimized {
+        text += " minimized";
+    }
+    if window.state.maximized {
+        text += " maximized";
+    }
+    if window.state.fullscreen {
+        text += " fullscreen";
+    }
+    if !window.state.visible {
+        text += " hidden";
+    }
+
+    text
+}
+
+/// Prints an event to the standard output, or an error to the standard error in the pretty format.
+pub fn print_record(record: &EventRecord, format: Format) -> io::Result<()> {
+    let mut stdout = io::stdout().lock();
+
+    match (format, record) {
+        (Format::Json, record) => {
+            serde_json::to_writer(&mut stdout, record)?;
+            writeln!(stdout)
+        }
+        (Format::Pretty, EventRecord::Available { window, event }) => {
+            writeln!(stdout, "{event:?} {}", describe_window(window))
+        }
+        (Format::Pretty, EventRecord::InitialState { window, event }) => {
+            writeln!(stdout, "{event:?} (initial) {}", describe_window(window))
+        }
+        (Format::Pretty, EventRecord::NotAvailable { event }) => writeln!(stdout, "{event:?}"),
+        (Format::Pretty, EventRecord::Error { error }) => {
+            eprintln!("error: {}", error.message);
+            Ok(())
+        }
+        (Format::Pretty, record) => writeln!(stdout, "{record:?}"),
+    }
+}
+
+/// Prints the windows captured by `snapshot`.
+pub fn print_snapshots(windows: &[WindowSnapshot], format: Format) -> io::Result<()> {
+    let mut stdout = io::stdout().lock();
+
+    match format {
+        Format::Json => {
+            serde_json::to_writer(&mut stdout, windows)?;
+            writeln!(stdout)
+        }
+        Format::Pretty => windows
+            .iter()
+            .try_for_each(|window| writeln!(stdout, "{}", describe_window(window))),
+    }
+}
+
+/// Prints the windows listed by `list`.
+pub fn print_windows(windows: &[ListedWindow], format: Format) -> io::Result<()> {
+    let mut stdout = io::stdout().lock();
+
+    match format {
+        Format::Json => {
+            serde_json::to_writer(&mut stdout, windows)?;
+            writeln!(stdout)
+        }
+        Format::Pretty => windows.iter().try_for_each(|window| {
+            writeln!(
+                stdout,
+                "{:?} id={} pid={} app={:?} {}",
+                window.title.as_deref().unwrap_or_default(),
+                window.id,
+                window.pid.map_or("?".to_owned(), |pid| pid.to_string()),
+                window.app.as_deref().unwrap_or_default(),
+                describe_bounds(window.bounds),
+            )
+        }),
+    }
+}
@@ -0,0 +1,375 @@
+//! Configuration of [`WindowObserver`] through a builder.
+
+use crate::{
+    BackpressurePolicy, CoalescingConfig, CoordinateSpace, Error, EventFilter, EventSender,
+    EventStream, ObserverOptions, WindowMatcher, WindowObserver, WindowRegistry, channel,
+    coalesce::CoalescingSink, executor::block_on, normalize::NormalizingSink,
+    platform_impl::PlatformWindowObserver, sink::FilteringSink,
+};
+
+/// Describes what the backend of the current platform supports.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    /// The events that the backend can dispatch.
+    pub events: EventFilter,
+    /// Whether the backend can observe a single window by [`Target::Window`].
+    pub window_target: bool,
+}
+
+impl Capabilities {
+    /// Returns the capabilities of the backend of the current platform.
+    pub fn current() -> Self {
+        PlatformWindowObserver::capabilities()
+    }
+}
+
+/// What a [`WindowObserver`] observes.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    /// All windows of the process that has the given ID.
+    Process(u32),
+    /// Only the window that has the given ID.
+    /// See [`WindowObserver::start_for_window`] for details.
+    #[cfg(any(target_os = "windows", feature = "macos-private-api"))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(target_os = "windows", feature = "macos-private-api")))
+    )]
+    Window(window_getter::WindowId),
+}
+
+/// A builder for [`WindowObserver`].
+///
+/// It is created by [`WindowObserver::builder`]. The target is required,
+/// and the event filter defaults to all events that the backend supports,
+/// which is [`Capabilities::events`].
+/// The channel is required by [`start`](Self::start),
+/// and [`start_stream`](Self::start_stream) creates its own channel instead.
+///
+/// There is no option to choose the backend because each platform has only one:
+/// the Accessibility API on macOS and `SetWinEventHook` on Windows.
+/// [`Capabilities::current`] describes what it supports.
+///
+/// # Example
+/// ```no_run
+/// # async fn example(pid: u32) -> Result<(), window_observer::Error> {
+/// use window_observer::{EventFilter, WindowObserver};
+///
+/// let (event_tx, event_rx) = tokio::sync::mpsc::unbounded_channel();
+/// let observer = WindowObserver::builder()
+///     .pid(pid)
+///     .filter(EventFilter {
+///         moved: true,
+///         resized: true,
+///         ..EventFilter::empty()
+///     })
+///     .channel(event_tx)
+///     .start()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct WindowObserverBuilder {
+    target: Option<Target>,
+    event_filter: EventFilter,
+    event_tx: Option<EventSender>,
+    backpressure: Option<(usize, BackpressurePolicy)>,
+    coalescing: Option<CoalescingConfig>,
+    registry: Option<WindowRegistry>,
+    matcher: Option<WindowMatcher>,
+    coordinate_space: CoordinateSpace,
+    initial_snapshot: bool,
+}
+
+impl Default for WindowObserverBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WindowObserverBuilder {
+    /// Creates a new builder with no target and no channel.
+    pub fn new() -> Self {
+        Self {
+            target: None,
+            event_filter: Capabilities::current().events,
+            event_tx: None,
+            backpressure: None,
+            coalescing: None,
+            registry: None,
+            matcher: None,
+            coordinate_space: CoordinateSpace::native(),
+            initial_snapshot: false,
+        }
+    }
+
+    /// Sets what the observer observes.
+    pub fn target(mut self, target: Target) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    /// Observes all windows of the process that has the given ID.
+    /// It is a shorthand of [`Target::Process`].
+    pub fn pid(self, pid: u32) -> Self {
+        self.target(Target::Process(pid))
+    }
+
+    /// Observes only the window that has the given ID.
+    /// It is a shorthand of [`Target::Window`].
+    #[cfg(any(target_os = "windows", feature = "macos-private-api"))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(target_os = "windows", feature = "macos-private-api")))
+    )]
+    pub fn window(self, window_id: window_getter::WindowId) -> Self {
+        self.target(Target::Window(window_id))
+    }
+
+    /// Sets the events to observe.
+    ///
+    /// Starting fails with [`Error::UnsupportedEvents`] if the filter enables events
+    /// that are not in [`Capabilities::events`]. Use [`EventFilter::intersection`]
+    /// with them to drop the unsupported events.
+    pub fn filter(mut self, event_filter: EventFilter) -> Self {
+        self.event_filter = event_filter;
+        self
+    }
+
+    /// Sets the channel that the events are sent to.
+    ///
+    /// It can be any [`EventSink`](crate::EventSink) such as an `EventTx`,
+    /// a bounded channel created by [`channel::bounded`] with a [`BackpressurePolicy`]
+    /// or a closure.
+    pub fn channel(mut self, event_tx: impl Into<EventSender>) -> Self {
+        self.event_tx = Some(event_tx.into());
+        self
+    }
+
+    /// Makes the channel created by [`start_stream`](Self::start_stream) bounded
+    /// with the given capacity and policy.
+    /// Use [`channel::bounded`] for the channel given to [`channel`](Self::channel).
+    pub fn backpressure(mut self, capacity: usize, policy: BackpressurePolicy) -> Self {
+        self.backpressure = Some((capacity, policy));
+        self
+    }
+
+    /// Coalesces the high-frequency events before they are sent to the channel,
+    /// so the discarded events never reach it. See [`coalesce`](crate::coalesce) for details.
+    ///
+    /// It applies to both [`start`](Self::start) and [`start_stream`](Self::start_stream),
+    /// and it is applied before the [`BackpressurePolicy`] of the channel.
+    pub fn coalescing(mut self, config: CoalescingConfig) -> Self {
+        self.coalescing = Some(config);
+        self
+    }
+
+    /// Keeps the registry up to date with the events.
+    ///
+    /// The events are applied to the registry after coalescing and before they are sent
+    /// to the channel, so the registry reflects the events that the consumer receives.
+    /// The registry also sends [`Event::OcclusionChanged`](crate::Event::OcclusionChanged)
+    /// if the event filter enables it. In that case, the backend also observes the stacking
+    /// order and the geometry of the windows, but those events are only sent to the channel
+    /// if the filter enables them.
+    /// Only the windows of the observed process are regarded as covering a window.
+    pub fn registry(mut self, registry: WindowRegistry) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    /// Sends only the events of the windows that the matcher matches.
+    ///
+    /// The matcher is evaluated by the backend before the event is sent,
+    /// so the registry and the coalescing only see the matched windows.
+    /// The events without a window such as [`Event::Closed`](crate::Event::Closed),
+    /// whose window can't be inspected anymore, are always sent.
+    pub fn matcher(mut self, matcher: WindowMatcher) -> Self {
+        self.matcher = Some(matcher);
+        self
+    }
+
+    /// Converts the bounds in the events into the coordinate space.
+    /// It defaults to [`CoordinateSpace::native`], which leaves them as they are.
+    ///
+    /// The bounds of a window are converted with the scale factor of the display that shows
+    /// the largest part of it, and the bounds of a display with its own scale factor.
+    /// The conversion is applied just before the events are sent to the channel,
+    /// so the values of [`Window`](crate::Window) and [`WindowRegistry`]
+    /// stay in the native coordinate space. Use [`Window::bounds_in`](crate::Window::bounds_in)
+    /// to query them in another one.
+    pub fn coordinate_space(mut self, space: CoordinateSpace) -> Self {
+        self.coordinate_space = space;
+        self
+    }
+
+    /// Sends the current state of the windows before any change when the observer starts.
+    ///
+    /// Each existing window gets [`Event::Created`](crate::Event::Created),
+    /// then [`Event::Showed`](crate::Event::Showed) or [`Event::Hidden`](crate::Event::Hidden),
+    /// and the focused window also gets [`Event::Focused`](crate::Event::Focused).
+    /// They are sent as [`MaybeWindowAvailable::InitialState`](crate::MaybeWindowAvailable::InitialState)
+    /// and only if the event filter enables them.
+    /// [`Event::InitialStateCompleted`](crate::Event::InitialStateCompleted) is sent after them.
+    ///
+    /// # Platform-specific
+    /// - **macOS:** A minimized window is hidden.
+    /// - **Windows:** Only the visible or minimized top-level windows are included,
+    ///   and a minimized window is hidden.
+    pub fn initial_snapshot(mut self, initial_snapshot: bool) -> Self {
+        self.initial_snapshot = initial_snapshot;
+        self
+    }
+
+    /// Checks that the options are complete and supported by the backend of the current platform
+    /// for [`start`](Self::start).
+    pub fn validate(&self) -> Result<(), Error> {
+        self.validate_channel(false)?;
+        self.validate_backend()
+    }
+
+    fn validate_channel(&self, for_stream: bool) -> Result<(), Error> {
+        if !for_stream {
+            if self.event_tx.is_none() {
+                return Err(Error::InvalidConfiguration("the channel is not set"));
+            }
+
+            if self.backpressure.is_some() {
+                return Err(Error::InvalidConfiguration(
+                    "the backpressure policy applies only to the channel created by the stream",
+                ));
+            }
+        } else if self.event_tx.is_some() {
+            return Err(Error::InvalidConfiguration(
+                "the stream creates its own channel, so the channel must not be set",
+            ));
+        }
+
+        if self.backpressure.is_some_and(|(capacity, _)| capacity == 0) {
+            return Err(Error::InvalidConfiguration(
+                "the capacity of the channel must be positive",
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn validate_backend(&self) -> Result<(), Error> {
+        let capabilities = Capabilities::current();
+
+        if self.target.is_none() {
+            return Err(Error::InvalidConfiguration("the target is not set"));
+        }
+
+        #[cfg(any(target_os = "windows", feature = "macos-private-api"))]
+        if matches!(self.target, Some(Target::Window(_))) && !capabilities.window_target {
+            return Err(Error::InvalidConfiguration(
+                "the backend does not support observing a single window",
+            ));
+        }
+
+        let unsupported = self.backend_filter().difference(&capabilities.events);
+        if !unsupported.is_empty() {
+            return Err(Error::UnsupportedEvents(unsupported));
+        }
+
+        if self.event_filter.is_empty() {
+            return Err(Error::InvalidConfiguration("no events are filtered"));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the events that the backend observes,
+    /// which include the ones that the registry needs for the occlusion.
+    fn backend_filter(&self) -> EventFilter {
+        let mut event_filter = self.event_filter;
+
+        // The occlusion is computed from the stacking order and the bounds of the windows.
+        if self.registry.is_some() && event_filter.occlusion_changed {
+            event_filter.stacking_changed = true;
+            event_filter.moved = true;
+            event_filter.resized = true;
+        }
+
+        event_filter
+    }
+
+    /// Validates the options and starts the observer.
+    pub async fn start(self) -> Result<WindowObserver, Error> {
+        self.validate()?;
+
+        let event_tx = self
+            .event_tx
+            .clone()
+            .expect("the channel is checked by `validate`");
+
+        self.start_with(event_tx).await
+    }
+
+    /// Validates the options and starts the observer by blocking the current thread.
+    /// It doesn't need an async runtime.
+    pub fn start_blocking(self) -> Result<WindowObserver, Error> {
+        block_on(self.start())
+    }
+
+    /// Validates the options and starts the observer with a channel owned by the returned stream.
+    /// The channel is bounded if [`backpressure`](Self::backpressure) is set.
+    pub async fn start_stream(self) -> Result<EventStream, Error> {
+        self.validate_channel(true)?;
+        self.validate_backend()?;
+
+        let (event_tx, event_rx) = match self.backpressure {
+            Some((capacity, policy)) => channel::bounded(capacity, policy),
+            None => channel::unbounded(),
+        };
+
+        let observer = self.start_with(event_tx.into()).await?;
+
+        Ok(EventStream::new(event_rx, observer))
+    }
+
+    async fn start_with(self, event_tx: EventSender) -> Result<WindowObserver, Error> {
+        let target = self.target.expect("the target is checked by `validate`");
+        let discarded = event_tx.discard_counter();
+        let backend_filter = self.backend_filter();
+        let event_tx = if self.coordinate_space == CoordinateSpace::native() {
+            event_tx
+        } else {
+            NormalizingSink::new(self.coordinate_space, event_tx).into()
+        };
+        // The events observed only for the registry don't reach the channel.
+        let dropped = backend_filter.difference(&self.event_filter);
+        let event_tx = if dropped.is_empty() {
+            event_tx
+        } else {
+            FilteringSink::new(dropped, event_tx).into()
+        };
+        let event_tx = match &self.registry {
+            Some(registry) => registry
+                .sink(event_tx)
+                .occlusion_events(self.event_filter.occlusion_changed)
+                .into(),
+            None => event_tx,
+        };
+        let event_tx = match self.coalescing {
+            Some(config) => CoalescingSink::new(config, event_tx).into(),
+            None => event_tx,
+        };
+        let options = ObserverOptions {
+            event_filter: backend_filter,
+            initial_snapshot: self.initial_snapshot,
+            matcher: self.matcher,
+        };
+
+        WindowObserver::start_with_options(
+            target,
+            event_tx.with_discard_counter(discarded),
+            options,
+        )
+        .await
+    }
+}
@@ -0,0 +1,688 @@
+//! Channels that deliver events from the observer to the consumer.
+//!
+//! The bounded channel created by [`bounded`] is an [`EventSink`](crate::EventSink)
+//! like the unbounded channel of tokio. It applies a [`BackpressurePolicy`]
+//! when the consumer is slower than the events, for example during a long window drag.
+
+use std::{
+    collections::VecDeque,
+    future::poll_fn,
+    mem::{Discriminant, discriminant},
+    sync::{Arc, Condvar, Mutex, MutexGuard, mpsc::RecvTimeoutError},
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+use crate::{Event, EventResult, MaybeWindowAvailable};
+
+/// What the bounded channel does when it is full.
+///
+/// Only [`Event::Moved`], [`Event::Resized`] and [`Event::TitleChanged`] are ever discarded,
+/// because a newer event of the same kind describes the window better.
+/// The other events, such as [`Event::Created`], [`Event::Closed`], [`Event::Focused`]
+/// and errors, are queued even if the channel is over its capacity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BackpressurePolicy {
+    /// The producer waits until the consumer receives an event.
+    ///
+    /// # Notes
+    /// The producer is the thread that receives events from the OS.
+    /// On macOS, it is shared by all observers, so a slow consumer delays the others too.
+    #[default]
+    Block,
+    /// The oldest queued geometry or title event is discarded.
+    DropOldest,
+    /// The new geometry or title event is discarded.
+    DropNewest,
+    /// A new geometry or title event replaces the queued event of the same kind
+    /// for the same window, so only the latest geometry and title per window are kept.
+    /// The replaced event is removed and the new one is queued at the back.
+    /// The replacing [`Event::TitleChanged`] takes `old` of the replaced one.
+    /// When the channel is still full, the oldest queued geometry or title event is discarded.
+    CoalesceGeometry,
+}
+
+/// The number of events discarded by each policy of a bounded channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DiscardedEvents {
+    /// Events discarded by [`BackpressurePolicy::DropOldest`]
+    /// and by [`BackpressurePolicy::CoalesceGeometry`] when coalescing was not enough.
+    pub dropped_oldest: u64,
+    /// Events discarded by [`BackpressurePolicy::DropNewest`].
+    pub dropped_newest: u64,
+    /// Events replaced by newer ones by [`BackpressurePolicy::CoalesceGeometry`].
+    pub coalesced: u64,
+}
+
+impl DiscardedEvents {
+    /// Returns the total number of discarded events.
+    pub fn total(&self) -> u64 {
+        self.dropped_oldest + self.dropped_newest + self.coalesced
+    }
+}
+
+struct State {
+    queue: VecDeque<EventResult>,
+    senders: usize,
+    receiver_alive: bool,
+    waker: Option<Waker>,
+    discarded: DiscardedEvents,
+}
+
+struct Shared {
+    capacity: usize,
+    policy: BackpressurePolicy,
+    state: Mutex<State>,
+    not_full: Condvar,
+    not_empty: Condvar,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Creates a bounded channel with the given capacity and policy.
+///
+/// # Panics
+/// It panics if `capacity` is zero.
+pub fn bounded(capacity: usize, policy: BackpressurePolicy) -> (BoundedEventTx, BoundedEventRx) {
+    assert!(capacity > 0, "the capacity of the channel must be positive");
+
+    with_capacity(capacity, policy)
+}
+
+/// Creates a channel of this module that never applies a backpressure policy.
+///
+/// It is useful for receiving events without an async runtime,
+/// because [`BoundedEventRx`] provides blocking methods.
+pub fn unbounded() -> (BoundedEventTx, BoundedEventRx) {
+    with_capacity(usize::MAX, BackpressurePolicy::Block)
+}
+
+fn with_capacity(capacity: usize, policy: BackpressurePolicy) -> (BoundedEventTx, BoundedEventRx) {
+    let shared = Arc::new(Shared {
+        capacity,
+        policy,
+        state: Mutex::new(State {
+            queue: VecDeque::new(),
+            senders: 1,
+            receiver_alive: true,
+            waker: None,
+            discarded: Default::default(),
+        }),
+        not_full: Condvar::new(),
+        not_empty: Condvar::new(),
+    });
+
+    (
+        BoundedEventTx {
+            shared: Arc::clone(&shared),
+        },
+        BoundedEventRx { shared },
+    )
+}
+
+/// Returns the window and the kind of the event if it is allowed to be discarded.
+pub(crate) fn coalescing_key(
+    result: &EventResult,
+) -> Option<(&crate::Window, Discriminant<Event>)> {
+    match result {
+        Ok(MaybeWindowAvailable::Available {
+            window,
+            event:
+                event @ (Event::Moved { .. } | Event::Resized { .. } | Event::TitleChanged { .. }),
+        }) => Some((window, discriminant(event))),
+        _ => None,
+    }
+}
+
+fn is_discardable(result: &EventResult) -> bool {
+    coalescing_key(result).is_some()
+}
+
+/// Makes the new event describe the change since before the replaced one.
+pub(crate) fn merge_replaced(replaced: EventResult, result: &mut EventResult) {
+    if let Ok(MaybeWindowAvailable::Available {
+        event: Event::TitleChanged { old: replaced, .. },
+        ..
+    }) = replaced
+        && let Ok(MaybeWindowAvailable::Available {
+            event: Event::TitleChanged { old, .. },
+            ..
+        }) = result
+    {
+        *old = replaced;
+    }
+}
+
+/// Reads the number of discarded events of a channel.
+/// Unlike [`BoundedEventTx`], it doesn't keep the channel open.
+#[derive(Clone)]
+pub(crate) struct DiscardCounter(Arc<Shared>);
+
+impl DiscardCounter {
+    pub fn get(&self) -> DiscardedEvents {
+        self.0.lock().discarded
+    }
+}
+
+/// The sending half of the channel created by [`bounded`] or [`unbounded`].
+pub struct BoundedEventTx {
+    shared: Arc<Shared>,
+}
+
+impl BoundedEventTx {
+    /// Sends an event according to the policy of the channel.
+    ///
+    /// It returns the event back if the receiver has been dropped.
+    pub fn send(&self, mut result: EventResult) -> Result<(), EventResult> {
+        let shared = &self.shared;
+        let mut state = shared.lock();
+
+        if !state.receiver_alive {
+            return Err(result);
+        }
+
+        if shared.policy == BackpressurePolicy::CoalesceGeometry
+            && let Some(index) = coalescing_key(&result).and_then(|key| {
+                state
+                    .queue
+                    .iter()
+                    .position(|queued| coalescing_key(queued) == Some(key))
+            })
+            && let Some(replaced) = state.queue.remove(index)
+        {
+            // The new event is queued at the back instead of the place of the replaced one,
+            // so that it is not received before the events that were sent before it.
+            merge_replaced(replaced, &mut result);
+            state.discarded.coalesced += 1;
+        }
+
+        if state.queue.len() >= shared.capacity {
+            match shared.policy {
+                BackpressurePolicy::Block => {
+                    state = shared
+                        .not_full
+                        .wait_while(state, |state| {
+                            state.receiver_alive && state.queue.len() >= shared.capacity
+                        })
+                        .unwrap_or_else(|e| e.into_inner());
+
+                    if !state.receiver_alive {
+                        return Err(result);
+                    }
+                }
+                BackpressurePolicy::DropNewest if is_discardable(&result) => {
+                    state.discarded.dropped_newest += 1;
+                    return Ok(());
+                }
+                BackpressurePolicy::DropNewest => {
+                    // The new event must not be dropped, so the newest geometry event makes room.
+                    if let Some(index) = state.queue.iter().rposition(is_discardable) {
+                        state.queue.remove(index);
+                        state.discarded.dropped_newest += 1;
+                    }
+                }
+                BackpressurePolicy::DropOldest | BackpressurePolicy::CoalesceGeometry => {
+                    if let Some(index) = state.queue.iter().position(is_discardable) {
+                        state.queue.remove(index);
+                        state.discarded.dropped_oldest += 1;
+                    } else if is_discardable(&result) {
+                        // Everything queued must be kept, so the new event is the oldest one
+                        // that can be discarded.
+                        state.discarded.dropped_oldest += 1;
+                        return Ok(());
+                    }
+                }
+            }
+        }
+
+        state.queue.push_back(result);
+        let waker = state.waker.take();
+        drop(state);
+
+        shared.not_empty.notify_one();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+
+        Ok(())
+    }
+
+    /// Returns the number of events discarded so far.
+    pub fn discarded(&self) -> DiscardedEvents {
+        self.shared.lock().discarded
+    }
+
+    /// Returns a handle that reads the discarded events without being a sender.
+    pub(crate) fn discard_counter(&self) -> DiscardCounter {
+        DiscardCounter(Arc::clone(&self.shared))
+    }
+
+    /// Returns `true` if the receiver has been dropped.
+    pub fn is_closed(&self) -> bool {
+        !self.shared.lock().receiver_alive
+    }
+}
+
+impl Clone for BoundedEventTx {
+    fn clone(&self) -> Self {
+        self.shared.lock().senders += 1;
+
+        Self {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl Drop for BoundedEventTx {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.senders -= 1;
+
+        if state.senders == 0 {
+            let waker = state.waker.take();
+            drop(state);
+
+            self.shared.not_empty.notify_all();
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for BoundedEventTx {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BoundedEventTx")
+            .field("capacity", &self.shared.capacity)
+            .field("policy", &self.shared.policy)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The receiving half of the channel created by [`bounded`] or [`unbounded`].
+///
+/// It doesn't depend on an async runtime.
+/// The events can be received with [`recv`](Self::recv) on any executor
+/// or with [`blocking_recv`](Self::blocking_recv) without an executor.
+pub struct BoundedEventRx {
+    shared: Arc<Shared>,
+}
+
+impl BoundedEventRx {
+    fn take(&self, state: &mut State) -> Option<EventResult> {
+        let result = state.queue.pop_front();
+
+        if result.is_some() {
+            self.shared.not_full.notify_one();
+        }
+
+        result
+    }
+
+    /// Polls to receive the next event.
+    ///
+    /// It returns `Poll::Ready(None)` when all senders have been dropped
+    /// and the queued events are received.
+    pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<EventResult>> {
+        let mut state = self.shared.lock();
+
+        if let Some(result) = self.take(&mut state) {
+            Poll::Ready(Some(result))
+        } else if state.senders == 0 {
+            Poll::Ready(None)
+        } else {
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+
+    /// Receives the next event.
+    ///
+    /// It returns `None` when all senders have been dropped and the queued events are received.
+    pub async fn recv(&mut self) -> Option<EventResult> {
+        poll_fn(|cx| self.poll_recv(cx)).await
+    }
+
+    /// Receives the next event by blocking the current thread.
+    ///
+    /// It returns `None` when all senders have been dropped and the queued events are received.
+    pub fn blocking_recv(&mut self) -> Option<EventResult> {
+        let mut state = self
+            .shared
+            .not_empty
+            .wait_while(self.shared.lock(), |state| {
+                state.queue.is_empty() && state.senders > 0
+            })
+            .unwrap_or_else(|e| e.into_inner());
+
+        self.take(&mut state)
+    }
+
+    /// Receives the next event by blocking the current thread for up to the given timeout.
+    pub fn recv_timeout(&mut self, timeout: Duration) -> Result<EventResult, RecvTimeoutError> {
+        let (mut state, _) = self
+            .shared
+            .not_empty
+            .wait_timeout_while(self.shared.lock(), timeout, |state| {
+                state.queue.is_empty() && state.senders > 0
+            })
+            .unwrap_or_else(|e| e.into_inner());
+
+        match self.take(&mut state) {
+            Some(result) => Ok(result),
+            None if state.senders == 0 => Err(RecvTimeoutError::Disconnected),
+            None => Err(RecvTimeoutError::Timeout),
+        }
+    }
+
+    /// Receives an event if one is queued.
+    pub fn try_recv(&mut self) -> Option<EventResult> {
+        let mut state = self.shared.lock();
+        self.take(&mut state)
+    }
+
+    /// Returns the number of queued events.
+    pub fn len(&self) -> usize {
+        self.shared.lock().queue.len()
+    }
+
+    /// Returns `true` if no events are queued.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of events discarded so far.
+    pub fn discarded(&self) -> DiscardedEvents {
+        self.shared.lock().discarded
+    }
+}
+
+impl Drop for BoundedEventRx {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.receiver_alive = false;
+        state.queue.clear();
+        drop(state);
+
+        // Wake up the producers waiting by `BackpressurePolicy::Block`.
+        self.shared.not_full.notify_all();
+    }
+}
+
+impl std::fmt::Debug for BoundedEventRx {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BoundedEventRx")
+            .field("capacity", &self.shared.capacity)
+            .field("policy", &self.shared.policy)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use super::*;
+    use crate::{Rect, Window, window::test_window};
+
+    fn available(window: &Window, event: Event) -> EventResult {
+        Ok(MaybeWindowAvailable::Available {
+            window: window.clone(),
+            event,
+        })
+    }
+
+    fn moved() -> Event {
+        Event::Moved {
+            bounds: Rect::default(),
+        }
+    }
+
+    fn resized() -> Event {
+        Event::Resized {
+            bounds: Rect::default(),
+        }
+    }
+
+    fn title_changed(window: &Window, old: &str, new: &str) -> EventResult {
+        available(
+            window,
+            Event::TitleChanged {
+                old: Some(old.to_owned()),
+                new: new.to_owned(),
+            },
+        )
+    }
+
+    fn drain(event_rx: &mut BoundedEventRx) -> Vec<EventResult> {
+        std::iter::from_fn(|| event_rx.try_recv()).collect()
+    }
+
+    fn is_event(result: &EventResult, expected: &Event) -> bool {
+        matches!(result, Ok(MaybeWindowAvailable::Available { event, .. }) if event == expected)
+    }
+
+    #[test]
+    fn coalescing_key_is_the_window_and_the_kind() {
+        let (a, b) = (test_window(1), test_window(2));
+
+        assert_eq!(
+            coalescing_key(&available(&a, moved())),
+            coalescing_key(&available(&a, moved())),
+        );
+        assert_eq!(
+            coalescing_key(&title_changed(&a, "1", "2")),
+            coalescing_key(&title_changed(&a, "3", "4")),
+        );
+        assert_ne!(
+            coalescing_key(&available(&a, moved())),
+            coalescing_key(&available(&a, resized())),
+        );
+        assert_ne!(
+            coalescing_key(&available(&a, moved())),
+            coalescing_key(&available(&b, moved())),
+        );
+    }
+
+    #[test]
+    fn coalescing_key_is_none_for_events_that_must_be_kept() {
+        let window = test_window(1);
+
+        assert!(coalescing_key(&available(&window, Event::Created)).is_none());
+        assert!(coalescing_key(&available(&window, Event::Focused)).is_none());
+        assert!(
+            coalescing_key(&Ok(MaybeWindowAvailable::NotAvailable {
+                event: Event::InitialStateCompleted,
+            }))
+            .is_none()
+        );
+        assert!(
+            coalescing_key(&Ok(MaybeWindowAvailable::InitialState {
+                window,
+                event: moved(),
+            }))
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn block_waits_for_the_receiver() {
+        let window = test_window(1);
+        let (event_tx, mut event_rx) = bounded(1, BackpressurePolicy::Block);
+        event_tx.send(available(&window, moved())).unwrap();
+
+        let producer = thread::spawn(move || {
+            event_tx.send(available(&window, resized())).unwrap();
+        });
+        thread::sleep(Duration::from_millis(50));
+        assert!(!producer.is_finished());
+        assert_eq!(event_rx.len(), 1);
+
+        assert!(is_event(&event_rx.blocking_recv().unwrap(), &moved()));
+        producer.join().unwrap();
+        assert!(is_event(&event_rx.blocking_recv().unwrap(), &resized()));
+        assert!(event_rx.blocking_recv().is_none());
+        assert_eq!(event_rx.discarded(), DiscardedEvents::default());
+    }
+
+    #[test]
+    fn block_returns_the_event_when_the_receiver_is_dropped() {
+        let window = test_window(1);
+        let (event_tx, event_rx) = bounded(1, BackpressurePolicy::Block);
+        event_tx.send(available(&window, moved())).unwrap();
+
+        let producer = thread::spawn(move || event_tx.send(available(&window, resized())));
+        thread::sleep(Duration::from_millis(50));
+        drop(event_rx);
+
+        assert!(producer.join().unwrap().is_err());
+    }
+
+    #[test]
+    fn drop_oldest_discards_the_oldest_geometry_event() {
+        let window = test_window(1);
+        let (event_tx, mut event_rx) = bounded(2, BackpressurePolicy::DropOldest);
+        event_tx.send(available(&window, Event::Created)).unwrap();
+        event_tx.send(available(&window, moved())).unwrap();
+        event_tx.send(available(&window, resized())).unwrap();
+
+        let events = drain(&mut event_rx);
+        assert_eq!(events.len(), 2);
+        assert!(is_event(&events[0], &Event::Created));
+        assert!(is_event(&events[1], &resized()));
+        assert_eq!(event_tx.discarded().dropped_oldest, 1);
+    }
+
+    #[test]
+    fn drop_oldest_keeps_the_events_that_must_be_kept() {
+        let window = test_window(1);
+        let (event_tx, mut event_rx) = bounded(1, BackpressurePolicy::DropOldest);
+        event_tx.send(available(&window, Event::Created)).unwrap();
+        event_tx.send(available(&window, moved())).unwrap();
+        event_tx.send(available(&window, Event::Focused)).unwrap();
+
+        let events = drain(&mut event_rx);
+        assert_eq!(events.len(), 2);
+        assert!(is_event(&events[0], &Event::Created));
+        assert!(is_event(&events[1], &Event::Focused));
+        assert_eq!(event_rx.discarded().dropped_oldest, 1);
+    }
+
+    #[test]
+    fn drop_newest_discards_the_new_geometry_event() {
+        let window = test_window(1);
+        let (event_tx, mut event_rx) = bounded(1, BackpressurePolicy::DropNewest);
+        event_tx.send(available(&window, moved())).unwrap();
+        event_tx.send(available(&window, resized())).unwrap();
+
+        let events = drain(&mut event_rx);
+        assert_eq!(events.len(), 1);
+        assert!(is_event(&events[0], &moved()));
+        assert_eq!(event_rx.discarded().dropped_newest, 1);
+    }
+
+    #[test]
+    fn drop_newest_makes_room_for_the_events_that_must_be_kept() {
+        let window = test_window(1);
+        let (event_tx, mut event_rx) = bounded(2, BackpressurePolicy::DropNewest);
+        event_tx.send(available(&window, moved())).unwrap();
+        event_tx.send(available(&window, resized())).unwrap();
+        event_tx
+            .send(available(
+                &window,
+                Event::Closed {
+                    window_id: 1.into(),
+                },
+            ))
+            .unwrap();
+
+        let events = drain(&mut event_rx);
+        assert_eq!(events.len(), 2);
+        assert!(is_event(&events[0], &moved()));
+        assert!(matches!(
+            &events[1],
+            Ok(MaybeWindowAvailable::Available {
+                event: Event::Closed { .. },
+                ..
+            })
+        ));
+        assert_eq!(event_rx.discarded().dropped_newest, 1);
+    }
+
+    #[test]
+    fn coalesce_geometry_replaces_the_event_of_the_same_window() {
+        let (a, b) = (test_window(1), test_window(2));
+        let (event_tx, mut event_rx) = bounded(8, BackpressurePolicy::CoalesceGeometry);
+        event_tx.send(title_changed(&a, "1", "2")).unwrap();
+        event_tx.send(available(&b, moved())).unwrap();
+        event_tx.send(title_changed(&a, "2", "3")).unwrap();
+        event_tx.send(available(&b, moved())).unwrap();
+
+        let events = drain(&mut event_rx);
+        assert_eq!(events.len(), 2);
+        assert!(is_event(
+            &events[0],
+            &Event::TitleChanged {
+                old: Some("1".to_owned()),
+                new: "3".to_owned(),
+            }
+        ));
+        assert!(is_event(&events[1], &moved()));
+        assert_eq!(event_rx.discarded().coalesced, 2);
+    }
+
+    #[test]
+    fn coalesce_geometry_keeps_the_order_of_the_events() {
+        let window = test_window(1);
+        let (event_tx, mut event_rx) = bounded(8, BackpressurePolicy::CoalesceGeometry);
+        event_tx.send(available(&window, moved())).unwrap();
+        event_tx
+            .send(available(
+                &window,
+                Event::MoveEnded {
+                    bounds: Rect::default(),
+                },
+            ))
+            .unwrap();
+        event_tx.send(available(&window, Event::Focused)).unwrap();
+        event_tx.send(available(&window, moved())).unwrap();
+
+        let events = drain(&mut event_rx);
+        assert_eq!(events.len(), 3);
+        assert!(is_event(
+            &events[0],
+            &Event::MoveEnded {
+                bounds: Rect::default(),
+            }
+        ));
+        assert!(is_event(&events[1], &Event::Focused));
+        assert!(is_event(&events[2], &moved()));
+        assert_eq!(event_rx.discarded().coalesced, 1);
+    }
+
+    #[test]
+    fn coalesce_geometry_drops_the_oldest_when_still_full() {
+        let (a, b) = (test_window(1), test_window(2));
+        let (event_tx, mut event_rx) = bounded(1, BackpressurePolicy::CoalesceGeometry);
+        event_tx.send(available(&a, moved())).unwrap();
+        event_tx.send(available(&b, moved())).unwrap();
+
+        let events = drain(&mut event_rx);
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            &events[0],
+            Ok(MaybeWindowAvailable::Available { window, .. }) if *window == b
+        ));
+        assert_eq!(
+            event_rx.discarded(),
+            DiscardedEvents {
+                dropped_oldest: 1,
+                ..Default::default()
+            }
+        );
+    }
+}
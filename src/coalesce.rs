@@ -0,0 +1,728 @@
+//! Coalescing of high-frequency events before they reach the channel.
+//!
+//! Live moves and resizes send an event for every frame. A [`CoalescingSink`] sits between
+//! the observer and the channel, so the events it discards never reach the consumer.
+//! It is set by [`WindowObserverBuilder::coalescing`](crate::WindowObserverBuilder::coalescing)
+//! or wraps a channel passed to [`WindowObserver::start`](crate::WindowObserver::start).
+//!
+//! The events are coalesced per window and per kind according to a [`CoalescingConfig`].
+//! The decisions are made by [`Coalescer`], which reads the time from a [`Clock`]
+//! so that it can be driven deterministically by [`ManualClock`].
+
+use std::{
+    mem::Discriminant,
+    sync::{Arc, Condvar, Mutex, MutexGuard},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    Event, EventResult, EventSender, EventSink, Window,
+    channel::{coalescing_key, merge_replaced},
+};
+
+/// How the events of a burst are coalesced.
+///
+/// A burst is a sequence of events of the same kind for the same window
+/// that are less than the interval of the [`CoalesceRule`] apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoalesceMode {
+    /// The first event of a burst is delivered immediately and the rest are discarded.
+    Leading,
+    /// Only the last event of a burst is delivered, after the window is quiet for the interval.
+    Trailing,
+    /// At most one event is delivered per interval.
+    /// The first event is delivered immediately and the last one at the end of the interval.
+    Throttle,
+}
+
+/// How the events of a kind are coalesced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CoalesceRule {
+    /// How the events of a burst are coalesced.
+    pub mode: CoalesceMode,
+    /// The quiet period that ends a burst, or the period of [`CoalesceMode::Throttle`].
+    pub interval: Duration,
+    /// The longest time the changes of a window are held back even if the burst continues.
+    ///
+    /// In [`CoalesceMode::Trailing`], the held event is delivered after it.
+    /// In [`CoalesceMode::Leading`], the next event is delivered after it.
+    /// [`CoalesceMode::Throttle`] never holds an event longer than the interval.
+    pub max_latency: Option<Duration>,
+}
+
+impl CoalesceRule {
+    /// Creates a rule of [`CoalesceMode::Leading`].
+    pub fn leading(interval: Duration) -> Self {
+        Self {
+            mode: CoalesceMode::Leading,
+            interval,
+            max_latency: None,
+        }
+    }
+
+    /// Creates a rule of [`CoalesceMode::Trailing`].
+    pub fn trailing(interval: Duration) -> Self {
+        Self {
+            mode: CoalesceMode::Trailing,
+            interval,
+            max_latency: None,
+        }
+    }
+
+    /// Creates a rule of [`CoalesceMode::Throttle`].
+    pub fn throttle(interval: Duration) -> Self {
+        Self {
+            mode: CoalesceMode::Throttle,
+            interval,
+            max_latency: None,
+        }
+    }
+
+    /// Sets the longest time the changes of a window are held back.
+    pub fn max_latency(mut self, max_latency: Duration) -> Self {
+        self.max_latency = Some(max_latency);
+        self
+    }
+}
+
+/// The rules of the events that can be coalesced.
+///
+/// Only [`Event::Moved`], [`Event::Resized`] and [`Event::TitleChanged`] can be coalesced,
+/// like the events discarded by [`BackpressurePolicy`](crate::BackpressurePolicy).
+/// The events without a rule are delivered as they are.
+/// A delivered [`Event::TitleChanged`] takes `old` of the events discarded before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CoalescingConfig {
+    /// The rule of [`Event::Moved`].
+    pub moved: Option<CoalesceRule>,
+    /// The rule of [`Event::Resized`].
+    pub resized: Option<CoalesceRule>,
+    /// The rule of [`Event::TitleChanged`].
+    pub title_changed: Option<CoalesceRule>,
+}
+
+impl CoalescingConfig {
+    /// Creates a config that applies the rule to [`Event::Moved`] and [`Event::Resized`].
+    pub fn geometry(rule: CoalesceRule) -> Self {
+        Self {
+            moved: Some(rule),
+            resized: Some(rule),
+            title_changed: None,
+        }
+    }
+
+    fn rule(&self, event: &Event) -> Option<CoalesceRule> {
+        match event {
+            Event::Moved { .. } => self.moved,
+            Event::Resized { .. } => self.resized,
+            Event::TitleChanged { .. } => self.title_changed,
+            _ => None,
+        }
+    }
+}
+
+/// The period at which the observers deliver [`Event::TitleChanged`] of a window at most.
+pub(crate) const TITLE_THROTTLE_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Wraps the sink of an interpreter so that [`Event::TitleChanged`] is throttled per window.
+///
+/// A title updated many times a second, for example by a progress counter,
+/// would otherwise flood the consumer. The last title of a burst is always delivered.
+pub(crate) fn throttle_titles(event_tx: EventSender) -> EventSender {
+    let config = CoalescingConfig {
+        title_changed: Some(CoalesceRule::throttle(TITLE_THROTTLE_INTERVAL)),
+        ..Default::default()
+    };
+
+    CoalescingSink::new(config, event_tx).into()
+}
+
+/// A source of the current time for [`Coalescer`].
+pub trait Clock: Send + Sync + 'static {
+    /// Returns the current time.
+    fn now(&self) -> Instant;
+}
+
+/// The clock of the system.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A clock that only advances when it is told to. It is useful for testing.
+#[derive(Debug, Clone)]
+pub struct ManualClock(Arc<Mutex<Instant>>);
+
+impl ManualClock {
+    /// Creates a clock stopped at the current time.
+    pub fn new() -> Self {
+        Self(Arc::new(Mutex::new(Instant::now())))
+    }
+
+    /// Advances the clock and its clones by the given duration.
+    pub fn advance(&self, duration: Duration) {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) += duration;
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// The state of the events of a kind for a window.
+struct Slot {
+    window: Window,
+    kind: Discriminant<Event>,
+    rule: CoalesceRule,
+    last_seen: Instant,
+    last_sent: Option<Instant>,
+    /// The event held back and the time since when its changes are held back.
+    pending: Option<(EventResult, Instant)>,
+}
+
+impl Slot {
+    /// Returns the time when the pending event must be delivered.
+    fn deadline(&self) -> Option<Instant> {
+        let (_, held_since) = self.pending.as_ref()?;
+
+        match self.rule.mode {
+            CoalesceMode::Leading => None,
+            CoalesceMode::Trailing => {
+                let quiet = self.last_seen + self.rule.interval;
+
+                Some(match self.rule.max_latency {
+                    Some(max_latency) => quiet.min(*held_since + max_latency),
+                    None => quiet,
+                })
+            }
+            CoalesceMode::Throttle => self
+                .last_sent
+                .map(|last_sent| last_sent + self.rule.interval),
+        }
+    }
+
+    /// Holds the event back in place of the pending one.
+    fn hold(&mut self, mut result: EventResult, now: Instant) {
+        let held_since = match self.pending.take() {
+            Some((replaced, held_since)) => {
+                merge_replaced(replaced, &mut result);
+                held_since
+            }
+            None => now,
+        };
+
+        self.pending = Some((result, held_since));
+    }
+
+    /// Merges the pending event into the event and takes it for delivery.
+    fn deliver(&mut self, mut result: EventResult, now: Instant) -> EventResult {
+        if let Some((replaced, _)) = self.pending.take() {
+            merge_replaced(replaced, &mut result);
+        }
+
+        self.last_sent = Some(now);
+        result
+    }
+}
+
+/// Decides which events are delivered according to a [`CoalescingConfig`].
+///
+/// It doesn't deliver events by itself. [`CoalescingSink`] calls
+/// [`push`](Self::push) for each event and [`flush_due`](Self::flush_due)
+/// at [`next_deadline`](Self::next_deadline).
+pub struct Coalescer {
+    config: CoalescingConfig,
+    clock: Arc<dyn Clock>,
+    slots: Vec<Slot>,
+    discarded: u64,
+}
+
+impl Coalescer {
+    /// Creates a new `Coalescer` with the clock of the system.
+    pub fn new(config: CoalescingConfig) -> Self {
+        Self::with_clock(config, Arc::new(SystemClock))
+    }
+
+    /// Creates a new `Coalescer` that reads the time from the given clock.
+    pub fn with_clock(config: CoalescingConfig, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            config,
+            clock,
+            slots: Vec::new(),
+            discarded: 0,
+        }
+    }
+
+    /// Takes an event and returns the events to deliver now in order.
+    ///
+    /// The events held back for the window of an event that is not coalesced are delivered
+    /// before it, so the order of the events of a window is kept. The events without a window
+    /// and errors deliver all the held events before them.
+    pub fn push(&mut self, result: EventResult) -> Vec<EventResult> {
+        let now = self.clock.now();
+        let mut delivered = self.flush_due_at(now);
+
+        let Some(rule) = result
+            .as_ref()
+            .ok()
+            .and_then(|payload| self.config.rule(payload.event()))
+        else {
+            let window = result.as_ref().ok().and_then(|payload| payload.window());
+            delivered.extend(self.take_pending(|slot| window.is_none_or(|w| slot.window == *w)));
+            delivered.push(result);
+            return delivered;
+        };
+        let (window, kind) = coalescing_key(&result).expect("the event has a rule");
+
+        let index = self
+            .slots
+            .iter()
+            .position(|slot| slot.window == *window && slot.kind == kind);
+        let in_burst = index.is_some_and(|index| {
+            now.duration_since(self.slots[index].last_seen) < self.slots[index].rule.interval
+        });
+        let index = index.unwrap_or_else(|| {
+            self.slots.push(Slot {
+                window: window.clone(),
+                kind,
+                rule,
+                last_seen: now,
+                last_sent: None,
+                pending: None,
+            });
+            self.slots.len() - 1
+        });
+        let slot = &mut self.slots[index];
+        slot.last_seen = now;
+
+        let deliver = match rule.mode {
+            CoalesceMode::Leading => {
+                !in_burst
+                    || rule.max_latency.is_some_and(|max_latency| {
+                        slot.last_sent
+                            .is_none_or(|last_sent| now.duration_since(last_sent) >= max_latency)
+                    })
+            }
+            CoalesceMode::Trailing => false,
+            CoalesceMode::Throttle => slot
+                .last_sent
+                .is_none_or(|last_sent| now.duration_since(last_sent) >= rule.interval),
+        };
+
+        if deliver {
+            delivered.push(slot.deliver(result, now));
+        } else {
+            // The held events of `Leading` are never delivered.
+            if rule.mode == CoalesceMode::Leading || slot.pending.is_some() {
+                self.discarded += 1;
+            }
+            slot.hold(result, now);
+        }
+
+        delivered
+    }
+
+    /// Returns the events whose time to be delivered has come.
+    pub fn flush_due(&mut self) -> Vec<EventResult> {
+        let now = self.clock.now();
+        self.flush_due_at(now)
+    }
+
+    fn flush_due_at(&mut self, now: Instant) -> Vec<EventResult> {
+        let mut delivered = Vec::new();
+
+        for slot in &mut self.slots {
+            if slot.deadline().is_some_and(|deadline| deadline <= now)
+                && let Some((result, _)) = slot.pending.take()
+            {
+                slot.last_sent = Some(now);
+                delivered.push(result);
+            }
+        }
+
+        // The slots of the windows that are quiet are no longer needed.
+        self.slots.retain(|slot| {
+            slot.deadline().is_some() || now.duration_since(slot.last_seen) < slot.rule.interval
+        });
+
+        delivered
+    }
+
+    /// Returns all held events regardless of their time. The discarded ones are not included.
+    pub fn flush_all(&mut self) -> Vec<EventResult> {
+        self.take_pending(|_| true)
+    }
+
+    fn take_pending(&mut self, mut predicate: impl FnMut(&Slot) -> bool) -> Vec<EventResult> {
+        let mut delivered = Vec::new();
+
+        for slot in &mut self.slots {
+            // The pending event of `Leading` is a discarded one.
+            if slot.rule.mode != CoalesceMode::Leading
+                && predicate(slot)
+                && let Some((result, _)) = slot.pending.take()
+            {
+                delivered.push(result);
+            }
+        }
+
+        delivered
+    }
+
+    /// Returns the earliest time when a held event must be delivered.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.slots.iter().filter_map(Slot::deadline).min()
+    }
+
+    /// Returns the number of events discarded so far.
+    pub fn discarded(&self) -> u64 {
+        self.discarded
+    }
+}
+
+impl std::fmt::Debug for Coalescer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Coalescer")
+            .field("config", &self.config)
+            .field("discarded", &self.discarded)
+            .finish_non_exhaustive()
+    }
+}
+
+struct State {
+    coalescer: Coalescer,
+    closed: bool,
+}
+
+struct Shared {
+    event_tx: EventSender,
+    state: Mutex<State>,
+    changed: Condvar,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Sends the events and returns `false` if the channel is closed.
+    fn send_all(&self, results: Vec<EventResult>) -> bool {
+        results.into_iter().all(|result| self.event_tx.send(result))
+    }
+}
+
+/// An [`EventSink`] that coalesces the events before sending them to another sink.
+///
+/// The held events are delivered by a thread at their deadlines,
+/// and the remaining ones are delivered when the sink is dropped.
+pub struct CoalescingSink {
+    shared: Arc<Shared>,
+}
+
+impl CoalescingSink {
+    /// Creates a new `CoalescingSink` that sends the coalesced events to the given sink.
+    pub fn new(config: CoalescingConfig, event_tx: impl Into<EventSender>) -> Self {
+        Self::with_clock(config, Arc::new(SystemClock), event_tx)
+    }
+
+    /// Creates a new `CoalescingSink` that reads the time from the given clock.
+    pub fn with_clock(
+        config: CoalescingConfig,
+        clock: Arc<dyn Clock>,
+        event_tx: impl Into<EventSender>,
+    ) -> Self {
+        let shared = Arc::new(Shared {
+            event_tx: event_tx.into(),
+            state: Mutex::new(State {
+                coalescer: Coalescer::with_clock(config, clock),
+                closed: false,
+            }),
+            changed: Condvar::new(),
+        });
+
+        thread::spawn({
+            let shared = Arc::clone(&shared);
+            move || flush_loop(&shared)
+        });
+
+        Self { shared }
+    }
+
+    /// Returns the number of events discarded so far.
+    pub fn discarded(&self) -> u64 {
+        self.shared.lock().coalescer.discarded()
+    }
+}
+
+/// Delivers the held events at their deadlines until the sink is dropped.
+fn flush_loop(shared: &Shared) {
+    let mut state = shared.lock();
+
+    while !state.closed {
+        let due = state.coalescer.flush_due();
+        if !due.is_empty() && !shared.send_all(due) {
+            return;
+        }
+
+        state = match state.coalescer.next_deadline() {
+            Some(deadline) => {
+                let timeout = deadline.saturating_duration_since(state.coalescer.clock.now());
+                shared
+                    .changed
+                    .wait_timeout(state, timeout)
+                    .unwrap_or_else(|e| e.into_inner())
+                    .0
+            }
+            None => shared
+                .changed
+                .wait(state)
+                .unwrap_or_else(|e| e.into_inner()),
+        };
+    }
+}
+
+impl EventSink for CoalescingSink {
+    fn send(&mut self, result: EventResult) -> bool {
+        // The lock is held while sending so that the flushing thread can't reorder the events.
+        let mut state = self.shared.lock();
+        let delivered = state.coalescer.push(result);
+        self.shared.changed.notify_one();
+
+        self.shared.send_all(delivered)
+    }
+}
+
+impl Drop for CoalescingSink {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.closed = true;
+        let pending = state.coalescer.flush_all();
+        self.shared.changed.notify_one();
+
+        self.shared.send_all(pending);
+    }
+}
+
+impl std::fmt::Debug for CoalescingSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CoalescingSink")
+            .field("coalescer", &self.shared.lock().coalescer)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MaybeWindowAvailable, Rect, window::test_window};
+
+    const INTERVAL: Duration = Duration::from_millis(100);
+
+    fn coalescer(rule: CoalesceRule) -> (Coalescer, ManualClock) {
+        let clock = ManualClock::new();
+        let config = CoalescingConfig {
+            title_changed: Some(rule),
+            ..CoalescingConfig::geometry(rule)
+        };
+
+        (
+            Coalescer::with_clock(config, Arc::new(clock.clone())),
+            clock,
+        )
+    }
+
+    fn available(window: &Window, event: Event) -> EventResult {
+        Ok(MaybeWindowAvailable::Available {
+            window: window.clone(),
+            event,
+        })
+    }
+
+    fn moved() -> Event {
+        Event::Moved {
+            bounds: Rect::default(),
+        }
+    }
+
+    fn resized() -> Event {
+        Event::Resized {
+            bounds: Rect::default(),
+        }
+    }
+
+    fn title_changed(window: &Window, old: &str, new: &str) -> EventResult {
+        available(
+            window,
+            Event::TitleChanged {
+                old: Some(old.to_owned()),
+                new: new.to_owned(),
+            },
+        )
+    }
+
+    fn events(results: Vec<EventResult>) -> Vec<Event> {
+        results
+            .into_iter()
+            .map(|result| result.unwrap().event().clone())
+            .collect()
+    }
+
+    fn titles(results: Vec<EventResult>) -> Vec<(String, String)> {
+        events(results)
+            .into_iter()
+            .map(|event| match event {
+                Event::TitleChanged { old, new } => (old.unwrap_or_default(), new),
+                event => panic!("unexpected event: {event:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn leading_delivers_the_first_event_of_a_burst() {
+        let (mut coalescer, clock) = coalescer(CoalesceRule::leading(INTERVAL));
+        let window = test_window(1);
+
+        assert_eq!(
+            titles(coalescer.push(title_changed(&window, "a", "b"))),
+            [("a".to_owned(), "b".to_owned())]
+        );
+        clock.advance(INTERVAL / 2);
+        assert!(coalescer.push(title_changed(&window, "b", "c")).is_empty());
+        clock.advance(INTERVAL * 2);
+        assert!(coalescer.flush_due().is_empty());
+
+        // The discarded events are never delivered, but the next burst starts a new one.
+        assert_eq!(
+            titles(coalescer.push(title_changed(&window, "c", "d"))),
+            [("c".to_owned(), "d".to_owned())]
+        );
+        assert!(coalescer.flush_all().is_empty());
+        assert_eq!(coalescer.discarded(), 1);
+    }
+
+    #[test]
+    fn leading_delivers_an_event_after_max_latency() {
+        let (mut coalescer, clock) =
+            coalescer(CoalesceRule::leading(INTERVAL).max_latency(INTERVAL * 3));
+        let window = test_window(1);
+        let mut delivered = 0;
+
+        // The burst never becomes quiet, so only `max_latency` lets the events through.
+        for _ in 0..10 {
+            delivered += coalescer.push(available(&window, moved())).len();
+            clock.advance(INTERVAL / 2);
+        }
+
+        assert_eq!(delivered, 2);
+    }
+
+    #[test]
+    fn trailing_delivers_the_last_event_after_the_quiet_period() {
+        let (mut coalescer, clock) = coalescer(CoalesceRule::trailing(INTERVAL));
+        let window = test_window(1);
+
+        assert!(coalescer.push(title_changed(&window, "a", "b")).is_empty());
+        clock.advance(INTERVAL / 2);
+        assert!(coalescer.push(title_changed(&window, "b", "c")).is_empty());
+        assert_eq!(
+            coalescer.next_deadline(),
+            Some(clock.now() + INTERVAL),
+            "the deadline is extended by the burst"
+        );
+
+        clock.advance(INTERVAL / 2);
+        assert!(coalescer.flush_due().is_empty());
+        clock.advance(INTERVAL / 2);
+        assert_eq!(
+            titles(coalescer.flush_due()),
+            [("a".to_owned(), "c".to_owned())]
+        );
+        assert_eq!(coalescer.next_deadline(), None);
+        assert_eq!(coalescer.discarded(), 1);
+    }
+
+    #[test]
+    fn trailing_delivers_the_held_event_after_max_latency() {
+        let (mut coalescer, clock) =
+            coalescer(CoalesceRule::trailing(INTERVAL).max_latency(INTERVAL * 2));
+        let window = test_window(1);
+        let mut delivered = Vec::new();
+
+        for _ in 0..5 {
+            delivered.extend(coalescer.push(available(&window, resized())));
+            clock.advance(INTERVAL / 2);
+        }
+
+        // The changes since the first event are held back for at most `max_latency`.
+        assert_eq!(events(delivered), [resized()]);
+    }
+
+    #[test]
+    fn throttle_delivers_at_most_one_event_per_interval() {
+        let (mut coalescer, clock) = coalescer(CoalesceRule::throttle(INTERVAL));
+        let window = test_window(1);
+
+        assert_eq!(
+            titles(coalescer.push(title_changed(&window, "a", "b"))),
+            [("a".to_owned(), "b".to_owned())]
+        );
+        clock.advance(INTERVAL / 4);
+        assert!(coalescer.push(title_changed(&window, "b", "c")).is_empty());
+        clock.advance(INTERVAL / 4);
+        assert!(coalescer.push(title_changed(&window, "c", "d")).is_empty());
+        assert_eq!(coalescer.next_deadline(), Some(clock.now() + INTERVAL / 2));
+
+        clock.advance(INTERVAL / 2);
+        assert_eq!(
+            titles(coalescer.flush_due()),
+            [("b".to_owned(), "d".to_owned())]
+        );
+        assert_eq!(coalescer.discarded(), 1);
+    }
+
+    #[test]
+    fn events_are_coalesced_per_window_and_kind() {
+        let (mut coalescer, _clock) = coalescer(CoalesceRule::trailing(INTERVAL));
+        let (a, b) = (test_window(1), test_window(2));
+
+        coalescer.push(available(&a, moved()));
+        coalescer.push(available(&a, resized()));
+        coalescer.push(available(&b, moved()));
+
+        assert_eq!(coalescer.flush_all().len(), 3);
+        assert_eq!(coalescer.discarded(), 0);
+    }
+
+    #[test]
+    fn other_events_of_the_window_deliver_the_held_ones_first() {
+        let (mut coalescer, _clock) = coalescer(CoalesceRule::trailing(INTERVAL));
+        let (a, b) = (test_window(1), test_window(2));
+
+        coalescer.push(available(&a, moved()));
+        coalescer.push(available(&b, moved()));
+
+        assert_eq!(
+            events(coalescer.push(available(&a, Event::Focused))),
+            [moved(), Event::Focused]
+        );
+        assert_eq!(
+            events(coalescer.push(Ok(MaybeWindowAvailable::NotAvailable {
+                event: Event::InitialStateCompleted,
+            }))),
+            [moved(), Event::InitialStateCompleted]
+        );
+    }
+}
@@ -0,0 +1,263 @@
+//! Classification and context of the errors.
+//!
+//! The errors sent through the channel are [`EventError`]s. They tell what failed
+//! and whether it is worth retrying by their [`ErrorKind`].
+
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use window_getter::WindowId;
+
+use crate::{
+    coalesce::{Clock, SystemClock},
+    platform_impl::{self, PlatformError},
+};
+
+/// The classification of an error.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ErrorKind {
+    /// The window was closed or destroyed before it was accessed.
+    /// It is common for a window that is closed while its event is handled.
+    WindowGone,
+    /// The permission to observe or control the windows is not granted.
+    PermissionDenied,
+    /// The process was not found or has exited.
+    ProcessNotFound,
+    /// The backend, the application or the window does not support the operation.
+    Unsupported,
+    /// The operation failed temporarily, for example because the application was busy.
+    Transient,
+    /// The error is not known to be recoverable.
+    Fatal,
+}
+
+impl ErrorKind {
+    /// Returns `true` if the observer keeps working after the error,
+    /// so the error can be ignored or the operation can be retried.
+    pub fn is_recoverable(&self) -> bool {
+        matches!(self, Self::WindowGone | Self::Transient)
+    }
+}
+
+/// An error that occurred while the observer was handling an event of the OS.
+///
+/// It is sent through the channel as the error of [`EventResult`](crate::EventResult).
+#[derive(Debug, thiserror::Error)]
+#[error("{kind:?} error while {operation}: {source}")]
+pub struct EventError {
+    kind: ErrorKind,
+    operation: &'static str,
+    window_id: Option<WindowId>,
+    raw_event: Option<String>,
+    suppressed: u32,
+    #[source]
+    source: PlatformError,
+}
+
+impl EventError {
+    /// Creates a new `EventError` with the kind classified from the platform-specific error.
+    pub(crate) fn new(operation: &'static str, source: PlatformError) -> Self {
+        Self {
+            kind: platform_impl::error_kind(&source),
+            operation,
+            window_id: None,
+            raw_event: None,
+            suppressed: 0,
+            source,
+        }
+    }
+
+    pub(crate) fn with_window_id(mut self, window_id: Option<WindowId>) -> Self {
+        self.window_id = window_id;
+        self
+    }
+
+    pub(crate) fn with_raw_event(mut self, raw_event: impl Into<String>) -> Self {
+        self.raw_event = Some(raw_event.into());
+        self
+    }
+
+    /// Returns the classification of the error.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Returns what the observer was doing, such as `"interpreting a notification"`.
+    pub fn operation(&self) -> &'static str {
+        self.operation
+    }
+
+    /// Returns the ID of the window that the event was about, if it is known.
+    ///
+    /// # Platform-specific
+    /// - **macOS:** It is always `None` without the `macos-private-api` feature.
+    pub fn window_id(&self) -> Option<WindowId> {
+        self.window_id
+    }
+
+    /// Returns the event of the OS that was being handled, if any.
+    ///
+    /// # Platform-specific
+    /// - **macOS:** It is the name of the notification of the Accessibility API.
+    /// - **Windows:** It is the debug representation of the type of the event of `SetWinEventHook`.
+    pub fn raw_event(&self) -> Option<&str> {
+        self.raw_event.as_deref()
+    }
+
+    /// Returns the number of the same errors that were not sent just before this one.
+    /// See [`EventError`] for the rate limit.
+    pub fn suppressed(&self) -> u32 {
+        self.suppressed
+    }
+
+    /// Returns the platform-specific error.
+    pub fn platform_error(&self) -> &PlatformError {
+        &self.source
+    }
+
+    /// Returns the key that tells whether two errors are the same.
+    fn key(&self) -> ErrorKey {
+        (
+            self.kind,
+            self.operation,
+            self.window_id,
+            self.raw_event.clone(),
+            self.source.to_string(),
+        )
+    }
+}
+
+type ErrorKey = (
+    ErrorKind,
+    &'static str,
+    Option<WindowId>,
+    Option<String>,
+    String,
+);
+
+/// The period in which the same error is sent only once.
+const RATE_LIMIT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Suppresses the same errors that repeat within [`RATE_LIMIT_INTERVAL`],
+/// so a broken window doesn't flood the channel with its errors.
+/// The next error sent after the interval tells how many were suppressed.
+pub(crate) struct ErrorRateLimiter {
+    clock: Arc<dyn Clock>,
+    /// The key of each recently sent error, when it was sent and the number of suppressed ones.
+    recent: Vec<(ErrorKey, Instant, u32)>,
+}
+
+impl Default for ErrorRateLimiter {
+    fn default() -> Self {
+        Self::with_clock(Arc::new(SystemClock))
+    }
+}
+
+impl ErrorRateLimiter {
+    /// Creates a new `ErrorRateLimiter` that reads the time from the given clock.
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Self {
+        Self {
+            clock,
+            recent: Vec::new(),
+        }
+    }
+
+    /// Returns the error if it should be sent now.
+    pub fn check(&mut self, mut error: EventError) -> Option<EventError> {
+        let now = self.clock.now();
+        let key = error.key();
+
+        match self.recent.iter_mut().find(|(recent, ..)| *recent == key) {
+            Some((_, sent_at, suppressed))
+                if now.duration_since(*sent_at) < RATE_LIMIT_INTERVAL =>
+            {
+                *suppressed += 1;
+                None
+            }
+            Some((_, sent_at, suppressed)) => {
+                error.suppressed = std::mem::take(suppressed);
+                *sent_at = now;
+                Some(error)
+            }
+            None => {
+                // The errors that can't be suppressed anymore are forgotten.
+                self.recent.retain(|(_, sent_at, suppressed)| {
+                    *suppressed > 0 || now.duration_since(*sent_at) < RATE_LIMIT_INTERVAL
+                });
+                self.recent.push((key, now, 0));
+                Some(error)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coalesce::ManualClock;
+
+    fn error(operation: &'static str) -> EventError {
+        #[cfg(target_os = "macos")]
+        let source = accessibility::Error::NotFound;
+        #[cfg(target_os = "windows")]
+        let source =
+            platform_impl::windows::error::WindowsError::IOError(std::io::Error::other("test"));
+
+        EventError::new(operation, source)
+    }
+
+    fn limiter() -> (ErrorRateLimiter, ManualClock) {
+        let clock = ManualClock::new();
+        (ErrorRateLimiter::with_clock(Arc::new(clock.clone())), clock)
+    }
+
+    #[test]
+    fn suppresses_the_same_error_within_the_interval() {
+        let (mut limiter, clock) = limiter();
+
+        assert!(limiter.check(error("a")).is_some());
+        assert!(limiter.check(error("a")).is_none());
+        clock.advance(RATE_LIMIT_INTERVAL / 2);
+        assert!(limiter.check(error("a")).is_none());
+
+        // A different error is not suppressed.
+        assert!(limiter.check(error("b")).is_some());
+    }
+
+    #[test]
+    fn the_next_error_carries_the_suppressed_count() {
+        let (mut limiter, clock) = limiter();
+        limiter.check(error("a"));
+        limiter.check(error("a"));
+        limiter.check(error("a"));
+
+        clock.advance(RATE_LIMIT_INTERVAL);
+        let error_after = limiter.check(error("a")).unwrap();
+        assert_eq!(error_after.suppressed(), 2);
+
+        // The count is reset once it has been reported.
+        clock.advance(RATE_LIMIT_INTERVAL);
+        assert_eq!(limiter.check(error("a")).unwrap().suppressed(), 0);
+    }
+
+    #[test]
+    fn forgets_the_errors_that_cant_be_suppressed_anymore() {
+        let (mut limiter, clock) = limiter();
+        limiter.check(error("a"));
+        limiter.check(error("b"));
+        limiter.check(error("b"));
+
+        clock.advance(RATE_LIMIT_INTERVAL);
+        limiter.check(error("c"));
+
+        // `a` has nothing to report, but `b` keeps its suppressed count.
+        let operations: Vec<_> = limiter.recent.iter().map(|(key, ..)| key.1).collect();
+        assert_eq!(operations, ["b", "c"]);
+    }
+}
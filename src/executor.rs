@@ -0,0 +1,162 @@
+//! A minimal executor for driving the observer's futures from plain threads.
+
+use std::{
+    future::Future,
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+};
+
+/// Wakes the thread that is blocked in [`block_on`].
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Runs a future to completion on the current thread.
+///
+/// The futures of this crate only wait on channels that are fulfilled by other threads,
+/// so they don't need a full async runtime to make progress.
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+
+        thread::park();
+    }
+}
+
+/// A channel that sends a single value from a thread to a future.
+#[cfg(target_os = "macos")]
+pub(crate) mod oneshot {
+    use std::{
+        future::Future,
+        pin::Pin,
+        sync::{Arc, Condvar, Mutex, MutexGuard},
+        task::{Context, Poll, Waker},
+        time::Duration,
+    };
+
+    struct State<T> {
+        value: Option<T>,
+        sent: bool,
+        senders: usize,
+        waker: Option<Waker>,
+    }
+
+    struct Shared<T> {
+        state: Mutex<State<T>>,
+        sent: Condvar,
+    }
+
+    impl<T> Shared<T> {
+        fn lock(&self) -> MutexGuard<'_, State<T>> {
+            self.state.lock().unwrap_or_else(|e| e.into_inner())
+        }
+    }
+
+    /// Creates a channel that sends a single value from a thread to a future.
+    ///
+    /// The sender can be cloned, and only the first value sent by any of the clones is received.
+    pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State {
+                value: None,
+                sent: false,
+                senders: 1,
+                waker: None,
+            }),
+            sent: Condvar::new(),
+        });
+
+        (Sender(Arc::clone(&shared)), Receiver(shared))
+    }
+
+    /// The sending half of the channel created by [`channel`].
+    pub struct Sender<T>(Arc<Shared<T>>);
+
+    impl<T> Sender<T> {
+        /// Sends the value unless a value has already been sent.
+        pub fn send(&self, value: T) {
+            let mut state = self.0.lock();
+            if state.sent {
+                return;
+            }
+
+            state.value = Some(value);
+            state.sent = true;
+            let waker = state.waker.take();
+            drop(state);
+
+            self.0.sent.notify_all();
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        }
+
+        /// Blocks the current thread until a value is sent or the timeout elapses.
+        /// It returns `true` if a value has been sent.
+        pub fn wait_timeout(&self, timeout: Duration) -> bool {
+            let (state, _) = self
+                .0
+                .sent
+                .wait_timeout_while(self.0.lock(), timeout, |state| !state.sent)
+                .unwrap_or_else(|e| e.into_inner());
+
+            state.sent
+        }
+    }
+
+    impl<T> Clone for Sender<T> {
+        fn clone(&self) -> Self {
+            self.0.lock().senders += 1;
+
+            Self(Arc::clone(&self.0))
+        }
+    }
+
+    impl<T> Drop for Sender<T> {
+        fn drop(&mut self) {
+            let mut state = self.0.lock();
+            state.senders -= 1;
+
+            if state.senders == 0
+                && let Some(waker) = state.waker.take()
+            {
+                drop(state);
+                waker.wake();
+            }
+        }
+    }
+
+    /// The receiving half of the channel created by [`channel`].
+    ///
+    /// It completes with the sent value, or with `None` if all senders are dropped without sending.
+    pub struct Receiver<T>(Arc<Shared<T>>);
+
+    impl<T> Future for Receiver<T> {
+        type Output = Option<T>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            let mut state = self.0.lock();
+
+            if let Some(value) = state.value.take() {
+                Poll::Ready(Some(value))
+            } else if state.sent || state.senders == 0 {
+                Poll::Ready(None)
+            } else {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
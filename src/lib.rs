@@ -3,11 +3,39 @@
 
 pub use window_getter;
 
+pub mod builder;
+pub mod channel;
+pub mod coalesce;
+pub mod error;
+mod executor;
+pub mod matcher;
+pub mod monitor;
+mod normalize;
 pub mod platform_impl;
+pub mod registry;
+pub mod sink;
+pub mod spatial;
+pub mod stream;
+pub mod supervisor;
 pub mod window;
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub mod wire;
 
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
 pub use ::tokio;
-pub use window::{Position, Size, Window};
+pub use builder::{Capabilities, Target, WindowObserverBuilder};
+pub use channel::{BackpressurePolicy, DiscardedEvents};
+pub use coalesce::{CoalesceMode, CoalesceRule, CoalescingConfig};
+pub use error::{ErrorKind, EventError};
+pub use matcher::{TextPattern, WindowMatcher, WindowProperties, WindowRole};
+pub use monitor::{Monitor, MonitorId};
+pub use registry::{RegistryChange, WindowEntry, WindowRegistry};
+pub use sink::{EventSender, EventSink};
+pub use stream::EventStream;
+pub use supervisor::{Backoff, ProcessMatcher, SupervisedWindowObserver};
+pub use window::{CoordinateSpace, Position, Rect, Size, Window, WindowAction};
 
 use crate::platform_impl::PlatformWindowObserver;
 
@@ -35,6 +63,20 @@ pub enum Error {
     /// - **Windows:** This does not occur on windows.
     #[error("The application does not support observing window")]
     NotSupported,
+    /// The window that has the given ID was not found.
+    #[error("The window was not found: {0:?}")]
+    WindowNotFound(window_getter::WindowId),
+    /// The options given to [`WindowObserverBuilder`] can't be used together
+    /// or are not supported by the backend.
+    #[error("The configuration is invalid: {0}")]
+    InvalidConfiguration(&'static str),
+    /// The filter enables events that the backend doesn't dispatch.
+    /// The value has the unsupported events. See [`Capabilities::events`].
+    #[error("The events are not supported by the backend: {0}")]
+    UnsupportedEvents(EventFilter),
+    /// The backend or the window does not support the action.
+    #[error("The action is not supported: {0:?}")]
+    UnsupportedAction(WindowAction),
     /// Permission denied error. This error only occurs on macOS.
     #[error("Permission denied.")]
     PermissionDenied,
@@ -43,8 +85,28 @@ pub enum Error {
     PlatformSpecificError(#[from] platform_impl::PlatformError),
 }
 
+impl Error {
+    /// Returns the classification of the error.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::InvalidProcessId(_) => ErrorKind::ProcessNotFound,
+            Self::SomethingWentWrong => ErrorKind::Transient,
+            Self::NotSupported | Self::UnsupportedEvents(_) | Self::UnsupportedAction(_) => {
+                ErrorKind::Unsupported
+            }
+            Self::WindowNotFound(_) => ErrorKind::WindowGone,
+            Self::InvalidConfiguration(_) => ErrorKind::Fatal,
+            Self::PermissionDenied => ErrorKind::PermissionDenied,
+            Self::PlatformSpecificError(e) => platform_impl::error_kind(e),
+        }
+    }
+}
+
 /// Represents a filter for window events.
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct EventFilter {
     /// Whether to observe [`Event::Foregrounded`] events.
     pub foregrounded: bool,
@@ -60,12 +122,52 @@ pub struct EventFilter {
     pub resized: bool,
     /// Whether to observe [`Event::Moved`] events.
     pub moved: bool,
+    /// Whether to observe [`Event::MoveStarted`] events.
+    pub move_started: bool,
+    /// Whether to observe [`Event::MoveEnded`] events.
+    pub move_ended: bool,
+    /// Whether to observe [`Event::ResizeStarted`] events.
+    pub resize_started: bool,
+    /// Whether to observe [`Event::ResizeEnded`] events.
+    pub resize_ended: bool,
     /// Whether to observe [`Event::Hidden`] events.
     pub hidden: bool,
     /// Whether to observe [`Event::Showed`] events.
     pub showed: bool,
     /// Whether to observe [`Event::Closed`] events.
     pub closed: bool,
+    /// Whether to observe [`Event::ProcessAttached`] events.
+    pub process_attached: bool,
+    /// Whether to observe [`Event::ProcessDetached`] events.
+    pub process_detached: bool,
+    /// Whether to observe [`Event::Lifecycle`] events.
+    pub lifecycle: bool,
+    /// Whether to observe [`Event::Minimized`] events.
+    pub minimized: bool,
+    /// Whether to observe [`Event::Restored`] events.
+    pub restored: bool,
+    /// Whether to observe [`Event::Maximized`] events.
+    pub maximized: bool,
+    /// Whether to observe [`Event::Unmaximized`] events.
+    pub unmaximized: bool,
+    /// Whether to observe [`Event::EnteredFullscreen`] events.
+    pub entered_fullscreen: bool,
+    /// Whether to observe [`Event::ExitedFullscreen`] events.
+    pub exited_fullscreen: bool,
+    /// Whether to observe [`Event::TitleChanged`] events.
+    pub title_changed: bool,
+    /// Whether to observe [`Event::MonitorChanged`] events.
+    pub monitor_changed: bool,
+    /// Whether to observe [`Event::StackingChanged`] events.
+    pub stacking_changed: bool,
+    /// Whether to observe [`Event::OcclusionChanged`] events.
+    pub occlusion_changed: bool,
+    /// Whether to observe [`Event::DisplayAdded`] events.
+    pub display_added: bool,
+    /// Whether to observe [`Event::DisplayRemoved`] events.
+    pub display_removed: bool,
+    /// Whether to observe [`Event::DisplayChanged`] events.
+    pub display_changed: bool,
 }
 
 impl EventFilter {
@@ -79,9 +181,29 @@ impl EventFilter {
             created: true,
             resized: true,
             moved: true,
+            move_started: true,
+            move_ended: true,
+            resize_started: true,
+            resize_ended: true,
             hidden: true,
             showed: true,
             closed: true,
+            process_attached: true,
+            process_detached: true,
+            lifecycle: true,
+            minimized: true,
+            restored: true,
+            maximized: true,
+            unmaximized: true,
+            entered_fullscreen: true,
+            exited_fullscreen: true,
+            title_changed: true,
+            monitor_changed: true,
+            stacking_changed: true,
+            occlusion_changed: true,
+            display_added: true,
+            display_removed: true,
+            display_changed: true,
         }
     }
 
@@ -90,20 +212,221 @@ impl EventFilter {
         Default::default()
     }
 
+    /// Returns `true` if no events are enabled.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::empty()
+    }
+
+    /// Creates a new `EventFilter` with the events enabled in both filters.
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self {
+            foregrounded: self.foregrounded && other.foregrounded,
+            backgrounded: self.backgrounded && other.backgrounded,
+            focused: self.focused && other.focused,
+            unfocused: self.unfocused && other.unfocused,
+            created: self.created && other.created,
+            resized: self.resized && other.resized,
+            moved: self.moved && other.moved,
+            move_started: self.move_started && other.move_started,
+            move_ended: self.move_ended && other.move_ended,
+            resize_started: self.resize_started && other.resize_started,
+            resize_ended: self.resize_ended && other.resize_ended,
+            hidden: self.hidden && other.hidden,
+            showed: self.showed && other.showed,
+            closed: self.closed && other.closed,
+            process_attached: self.process_attached && other.process_attached,
+            process_detached: self.process_detached && other.process_detached,
+            lifecycle: self.lifecycle && other.lifecycle,
+            minimized: self.minimized && other.minimized,
+            restored: self.restored && other.restored,
+            maximized: self.maximized && other.maximized,
+            unmaximized: self.unmaximized && other.unmaximized,
+            entered_fullscreen: self.entered_fullscreen && other.entered_fullscreen,
+            exited_fullscreen: self.exited_fullscreen && other.exited_fullscreen,
+            title_changed: self.title_changed && other.title_changed,
+            monitor_changed: self.monitor_changed && other.monitor_changed,
+            stacking_changed: self.stacking_changed && other.stacking_changed,
+            occlusion_changed: self.occlusion_changed && other.occlusion_changed,
+            display_added: self.display_added && other.display_added,
+            display_removed: self.display_removed && other.display_removed,
+            display_changed: self.display_changed && other.display_changed,
+        }
+    }
+
+    /// Creates a new `EventFilter` with the events enabled in `self` but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = *self;
+
+        for name in EVENT_NAMES {
+            if other.flag(name) {
+                *difference.flag_mut(name).expect("the name is a field") = false;
+            }
+        }
+
+        difference
+    }
+
+    /// Returns whether the event that has the name of the field is enabled.
+    fn flag(&self, name: &str) -> bool {
+        let mut filter = *self;
+
+        filter.flag_mut(name).is_some_and(|flag| *flag)
+    }
+
+    /// Returns the flag of the event that has the name of the field, such as `"moved"`.
+    fn flag_mut(&mut self, name: &str) -> Option<&mut bool> {
+        Some(match name {
+            "foregrounded" => &mut self.foregrounded,
+            "backgrounded" => &mut self.backgrounded,
+            "focused" => &mut self.focused,
+            "unfocused" => &mut self.unfocused,
+            "created" => &mut self.created,
+            "resized" => &mut self.resized,
+            "moved" => &mut self.moved,
+            "move_started" => &mut self.move_started,
+            "move_ended" => &mut self.move_ended,
+            "resize_started" => &mut self.resize_started,
+            "resize_ended" => &mut self.resize_ended,
+            "hidden" => &mut self.hidden,
+            "showed" => &mut self.showed,
+            "closed" => &mut self.closed,
+            "process_attached" => &mut self.process_attached,
+            "process_detached" => &mut self.process_detached,
+            "lifecycle" => &mut self.lifecycle,
+            "minimized" => &mut self.minimized,
+            "restored" => &mut self.restored,
+            "maximized" => &mut self.maximized,
+            "unmaximized" => &mut self.unmaximized,
+            "entered_fullscreen" => &mut self.entered_fullscreen,
+            "exited_fullscreen" => &mut self.exited_fullscreen,
+            "title_changed" => &mut self.title_changed,
+            "monitor_changed" => &mut self.monitor_changed,
+            "stacking_changed" => &mut self.stacking_changed,
+            "occlusion_changed" => &mut self.occlusion_changed,
+            "display_added" => &mut self.display_added,
+            "display_removed" => &mut self.display_removed,
+            "display_changed" => &mut self.display_changed,
+            _ => return None,
+        })
+    }
+
     pub(crate) fn should_dispatch(&self, event: &Event) -> bool {
         matches!(event, Event::Foregrounded) && self.foregrounded
             || matches!(event, Event::Backgrounded) && self.backgrounded
             || matches!(event, Event::Focused) && self.focused
             || matches!(event, Event::Unfocused) && self.unfocused
             || matches!(event, Event::Created) && self.created
-            || matches!(event, Event::Resized) && self.resized
-            || matches!(event, Event::Moved) && self.moved
+            || matches!(event, Event::Resized { .. }) && self.resized
+            || matches!(event, Event::Moved { .. }) && self.moved
+            || matches!(event, Event::MoveStarted) && self.move_started
+            || matches!(event, Event::MoveEnded { .. }) && self.move_ended
+            || matches!(event, Event::ResizeStarted) && self.resize_started
+            || matches!(event, Event::ResizeEnded { .. }) && self.resize_ended
             || matches!(event, Event::Hidden) && self.hidden
             || matches!(event, Event::Showed) && self.showed
             || matches!(event, Event::Closed { .. }) && self.closed
+            || matches!(event, Event::ProcessAttached { .. }) && self.process_attached
+            || matches!(event, Event::ProcessDetached { .. }) && self.process_detached
+            || matches!(event, Event::Lifecycle { .. }) && self.lifecycle
+            || matches!(event, Event::Minimized) && self.minimized
+            || matches!(event, Event::Restored) && self.restored
+            || matches!(event, Event::Maximized) && self.maximized
+            || matches!(event, Event::Unmaximized) && self.unmaximized
+            || matches!(event, Event::EnteredFullscreen) && self.entered_fullscreen
+            || matches!(event, Event::ExitedFullscreen) && self.exited_fullscreen
+            || matches!(event, Event::TitleChanged { .. }) && self.title_changed
+            || matches!(event, Event::MonitorChanged { .. }) && self.monitor_changed
+            || matches!(event, Event::StackingChanged { .. }) && self.stacking_changed
+            || matches!(event, Event::OcclusionChanged { .. }) && self.occlusion_changed
+            || matches!(event, Event::DisplayAdded { .. }) && self.display_added
+            || matches!(event, Event::DisplayRemoved { .. }) && self.display_removed
+            || matches!(event, Event::DisplayChanged { .. }) && self.display_changed
     }
 }
 
+/// The names of the fields of [`EventFilter`].
+const EVENT_NAMES: [&str; 30] = [
+    "foregrounded",
+    "backgrounded",
+    "focused",
+    "unfocused",
+    "created",
+    "resized",
+    "moved",
+    "move_started",
+    "move_ended",
+    "resize_started",
+    "resize_ended",
+    "hidden",
+    "showed",
+    "closed",
+    "process_attached",
+    "process_detached",
+    "lifecycle",
+    "minimized",
+    "restored",
+    "maximized",
+    "unmaximized",
+    "entered_fullscreen",
+    "exited_fullscreen",
+    "title_changed",
+    "monitor_changed",
+    "stacking_changed",
+    "occlusion_changed",
+    "display_added",
+    "display_removed",
+    "display_changed",
+];
+
+/// Formats the enabled events as a comma-separated list of the names of the fields,
+/// which can be parsed back by [`FromStr`](std::str::FromStr).
+impl std::fmt::Display for EventFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<_> = EVENT_NAMES
+            .into_iter()
+            .filter(|name| self.flag(name))
+            .collect();
+
+        f.write_str(&names.join(","))
+    }
+}
+
+/// Parses a comma-separated list of the names of the fields, such as `"moved,resized"`.
+/// `"all"` enables all events, and an empty string enables none.
+///
+/// # Example
+/// ```
+/// use window_observer::EventFilter;
+///
+/// let filter: EventFilter = "moved, resized".parse().unwrap();
+/// assert!(filter.moved && filter.resized && !filter.focused);
+/// assert!("teleported".parse::<EventFilter>().is_err());
+/// ```
+impl std::str::FromStr for EventFilter {
+    type Err = ParseEventFilterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut filter = Self::empty();
+
+        for name in s.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+            if name == "all" {
+                filter = Self::all();
+            } else {
+                *filter
+                    .flag_mut(name)
+                    .ok_or_else(|| ParseEventFilterError(name.to_owned()))? = true;
+            }
+        }
+
+        Ok(filter)
+    }
+}
+
+/// The error of parsing an [`EventFilter`] from a string that has an unknown event name.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Unknown event name: {0}")]
+pub struct ParseEventFilterError(pub String);
+
 /// Represents events that can be observed on a window.
 #[non_exhaustive]
 #[derive(Debug, Clone, PartialEq)]
@@ -111,9 +434,38 @@ pub enum Event {
     /// The window was created.
     Created,
     /// The window was resized.
-    Resized,
+    Resized {
+        /// The new bounds of the window in the native coordinate space.
+        bounds: Rect,
+    },
     /// The window was moved.
-    Moved,
+    Moved {
+        /// The new bounds of the window in the native coordinate space.
+        bounds: Rect,
+    },
+    /// The window started being moved. It precedes the [`Event::Moved`] events of the move.
+    ///
+    /// # Platform-specific
+    /// - **macOS:** There is no notification about the start and the end of a move,
+    ///   so the first [`Event::Moved`] event of the window starts a move and the move ends
+    ///   when the window is not moved for a while and the left mouse button is released.
+    ///   A resize from the left or the top edge may be reported as a move.
+    /// - **Windows:** Only the moves by the user are reported,
+    ///   which start with `EVENT_SYSTEM_MOVESIZESTART` and end with `EVENT_SYSTEM_MOVESIZEEND`.
+    MoveStarted,
+    /// The window finished being moved. See [`Event::MoveStarted`] for details.
+    MoveEnded {
+        /// The final bounds of the window in the native coordinate space.
+        bounds: Rect,
+    },
+    /// The window started being resized. It precedes the [`Event::Resized`] events of the resize.
+    /// See [`Event::MoveStarted`] for the platform-specific details.
+    ResizeStarted,
+    /// The window finished being resized. See [`Event::MoveStarted`] for details.
+    ResizeEnded {
+        /// The final bounds of the window in the native coordinate space.
+        bounds: Rect,
+    },
     /// The window was brought to the foreground.
     /// This event does not mean the window has gained input focus.
     Foregrounded,
@@ -136,17 +488,179 @@ pub enum Event {
     ///   Therefore, this event will not be dispatched when the window is miniaturized
     Unfocused,
     /// The window was hidden.
+    ///
+    /// It is also dispatched together with [`Event::Minimized`].
     Hidden,
     /// The window was showed.
-    /// 
+    ///
+    /// It is also dispatched together with [`Event::Restored`].
+    ///
     /// # Platform-specific
     /// - **Windows:** This event may not occur until after the observer has started
     ///   and the first Hidden event has been triggered.
     ///   Example: When monitoring begins while the window is minimized to the taskbar,
     ///   and then the window is opened from the taskbar.
     Showed,
+    /// The window was minimized.
+    ///
+    /// # Platform-specific
+    /// - **macOS:** It is dispatched when the window is miniaturized into the Dock.
+    Minimized,
+    /// The window was restored from the minimized state.
+    Restored,
+    /// The window was maximized.
+    ///
+    /// # Platform-specific
+    /// - **macOS:** This event is not supported because macOS has no maximized state.
+    Maximized,
+    /// The window was restored from the maximized state.
+    ///
+    /// # Platform-specific
+    /// - **macOS:** This event is not supported because macOS has no maximized state.
+    Unmaximized,
+    /// The window entered the fullscreen mode.
+    ///
+    /// # Platform-specific
+    /// - **Windows:** A window is regarded as fullscreen when it covers its whole monitor
+    ///   without being maximized, because Windows has no fullscreen state.
+    EnteredFullscreen,
+    /// The window exited the fullscreen mode.
+    ///
+    /// # Platform-specific
+    /// - **Windows:** See [`Event::EnteredFullscreen`].
+    ExitedFullscreen,
+    /// The title of the window was changed.
+    ///
+    /// `old` is `None` if the title was not known to the observer.
+    /// The event is not dispatched when the title is set to the same value again.
+    /// It is throttled per window to one event every 250 milliseconds,
+    /// and the last title of a burst is delivered at the end of the period,
+    /// so `old` is always the title delivered before it.
+    /// A window without a title has an empty title.
+    TitleChanged { old: Option<String>, new: String },
+    /// The display that shows the largest part of the window was changed,
+    /// for example by dragging the window to another display.
+    /// It is checked when the window is moved or resized.
+    /// See [`Window::current_monitor`] for details.
+    MonitorChanged {
+        from: Box<Monitor>,
+        to: Box<Monitor>,
+    },
+    /// The stacking order of the windows of the process was changed.
+    /// It is sent as [`MaybeWindowAvailable::NotAvailable`].
+    /// See [`Window::z_index`] for the platform-specific details of the order.
+    ///
+    /// The order is checked when a window is focused, created, closed, shown or hidden,
+    /// so a change by the other events such as moving a window behind another one
+    /// without focusing it is reported with the next of them.
+    StackingChanged {
+        /// The windows from the front to the back.
+        windows: Vec<Window>,
+    },
+    /// The window became mostly covered by the other windows or uncovered.
+    /// A window is mostly covered when less than [`spatial::MOSTLY_COVERED_THRESHOLD`]
+    /// of its area is visible. A hidden or minimized window is covered.
+    ///
+    /// It is computed by a [`WindowRegistry`] from the bounds and the stacking order of the windows,
+    /// so it is only sent when the observer has a registry.
+    /// See [`WindowObserverBuilder::registry`] and [`WindowRegistry::visible_fraction`].
+    /// The observer also observes [`Event::StackingChanged`], [`Event::Moved`]
+    /// and [`Event::Resized`] for the registry, but they are only sent to the channel
+    /// if the filter enables them.
+    ///
+    /// # Notes
+    /// Only the windows of the observed process are regarded as covering the window,
+    /// because the windows of the other processes are not observed.
+    OcclusionChanged {
+        /// The fraction of the area of the window that is visible, from `0.0` to `1.0`.
+        visible_fraction: f64,
+    },
+    /// A display was connected.
+    ///
+    /// The display events are sent as [`MaybeWindowAvailable::NotAvailable`].
+    /// The displays are compared with the last known ones before the observer sends
+    /// the geometry events of the windows, so the display events precede the [`Event::Moved`]
+    /// and [`Event::Resized`] events of the windows moved by the change.
+    /// They are also compared when the other events are received, at most twice a second.
+    ///
+    /// # Platform-specific
+    /// - **macOS:** Only the notifications of the observed application are received,
+    ///   so a change is reported with the next event of the application.
+    /// - **Windows:** The events of all windows are received,
+    ///   so a change is reported soon even if the observed application is idle.
+    DisplayAdded { monitor: Box<Monitor> },
+    /// A display was disconnected. See [`Event::DisplayAdded`] for details.
+    DisplayRemoved { monitor: Box<Monitor> },
+    /// The bounds, the work area or the scale factor of a display was changed.
+    /// See [`Event::DisplayAdded`] for details.
+    DisplayChanged {
+        from: Box<Monitor>,
+        to: Box<Monitor>,
+    },
     /// The window was closed.
     Closed { window_id: window_getter::WindowId },
+    /// A [`SupervisedWindowObserver`] attached to a process that matches its [`ProcessMatcher`].
+    /// Window events of the process follow this event.
+    ProcessAttached { pid: u32 },
+    /// The process that a [`SupervisedWindowObserver`] was attached to has exited.
+    /// The observer waits for the next matching process after this event.
+    ProcessDetached { pid: u32 },
+    /// The state of the observer itself was changed. See [`ObserverEvent`] for details.
+    Lifecycle { event: ObserverEvent },
+    /// All events of the initial state snapshot have been sent.
+    /// See [`WindowObserverBuilder::initial_snapshot`] for details.
+    ///
+    /// It is sent as [`MaybeWindowAvailable::NotAvailable`] regardless of the event filter.
+    InitialStateCompleted,
+}
+
+/// Describes the state of an observer. It is sent as [`Event::Lifecycle`].
+///
+/// An observer sends [`Started`](Self::Started) first and [`Stopped`](Self::Stopped) last,
+/// and the others between them.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "snake_case"))]
+pub enum ObserverEvent {
+    /// The observer started receiving events of the OS.
+    /// It precedes the events of the initial state snapshot.
+    Started,
+    /// The observer may be missing events of the OS.
+    Degraded { reason: DegradedReason },
+    /// The observer receives events of the OS again after [`ObserverEvent::Degraded`].
+    ///
+    /// # Platform-specific
+    /// - **Windows:** The backend can't recover by itself,
+    ///   so it is only sent by [`SupervisedWindowObserver`] after it restarted the backend.
+    Recovered,
+    /// The observed process has exited. No events will be sent for its windows anymore.
+    TargetExited { pid: u32 },
+    /// The observer was stopped by [`WindowObserver::stop`].
+    /// The events that were pending at that time precede it, and nothing follows it.
+    /// It is not sent if the observer is dropped without being stopped.
+    Stopped,
+}
+
+/// Why an observer was degraded. See [`ObserverEvent::Degraded`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum DegradedReason {
+    /// The application doesn't respond to the requests of the observer.
+    ///
+    /// # Platform-specific
+    /// - **Windows:** This does not occur on Windows.
+    NotResponding,
+    /// The backend stopped receiving events of the OS by itself.
+    ///
+    /// # Platform-specific
+    /// - **macOS:** This does not occur on macOS.
+    /// - **Windows:** It occurs when the thread of the hook has ended.
+    BackendEnded,
 }
 
 /// Represents a window that may or may not be available.
@@ -157,37 +671,204 @@ pub enum MaybeWindowAvailable {
     /// The window is not available.
     /// This can happen when the window is closed.
     NotAvailable { event: Event },
+    /// The event describes the state of the window when the observer started
+    /// instead of a change. See [`WindowObserverBuilder::initial_snapshot`] for details.
+    InitialState { window: Window, event: Event },
+}
+
+impl MaybeWindowAvailable {
+    /// Returns the event.
+    pub fn event(&self) -> &Event {
+        match self {
+            Self::Available { event, .. }
+            | Self::NotAvailable { event }
+            | Self::InitialState { event, .. } => event,
+        }
+    }
+
+    /// Returns the window if it is available.
+    pub fn window(&self) -> Option<&Window> {
+        match self {
+            Self::Available { window, .. } | Self::InitialState { window, .. } => Some(window),
+            Self::NotAvailable { .. } => None,
+        }
+    }
+
+    /// Returns `true` if the event is a part of the initial state snapshot,
+    /// including [`Event::InitialStateCompleted`].
+    pub fn is_initial_state(&self) -> bool {
+        matches!(
+            self,
+            Self::InitialState { .. }
+                | Self::NotAvailable {
+                    event: Event::InitialStateCompleted
+                }
+        )
+    }
 }
 
 /// A type alias for the result of an event.
 /// `Err` means that the event could not be processed, and `Ok` contains the event.
-pub type EventResult = Result<MaybeWindowAvailable, platform_impl::PlatformError>;
+/// The same errors that repeat in a short period are sent only once. See [`EventError::suppressed`].
+pub type EventResult = Result<MaybeWindowAvailable, EventError>;
 /// A type alias for the window event transmission channel.
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
 pub type EventTx = tokio::sync::mpsc::UnboundedSender<EventResult>;
 /// A type alias for the window event reception channel.
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
 pub type EventRx = tokio::sync::mpsc::UnboundedReceiver<EventResult>;
 
+/// The options that are passed to the backend of the platform.
+#[derive(Debug, Clone)]
+pub(crate) struct ObserverOptions {
+    pub event_filter: EventFilter,
+    pub initial_snapshot: bool,
+    /// The windows whose events are sent. It is `None` if all windows are.
+    pub matcher: Option<WindowMatcher>,
+}
+
+impl From<EventFilter> for ObserverOptions {
+    fn from(event_filter: EventFilter) -> Self {
+        Self {
+            event_filter,
+            initial_snapshot: false,
+            matcher: None,
+        }
+    }
+}
+
 /// Observes window events.
-pub struct WindowObserver(PlatformWindowObserver);
+pub struct WindowObserver {
+    inner: PlatformWindowObserver,
+    discarded: Option<channel::DiscardCounter>,
+}
 
 impl WindowObserver {
+    /// Creates a [`WindowObserverBuilder`] for configuring and starting an observer.
+    pub fn builder() -> WindowObserverBuilder {
+        WindowObserverBuilder::new()
+    }
+
     /// Creates a new [`WindowObserver`] for a given process ID and event channel
     /// and start the observer.
+    ///
+    /// `event_tx` can be any [`EventSink`] such as an `EventTx`, a bounded channel created by
+    /// [`channel::bounded`] or a closure.
+    /// The returned future doesn't need a specific async runtime,
+    /// and [`start_blocking`](Self::start_blocking) doesn't need any.
     pub async fn start(
         pid: u32,
-        event_tx: EventTx,
+        event_tx: impl Into<EventSender>,
         event_filter: EventFilter,
     ) -> Result<Self, Error> {
-        #[cfg(target_os = "macos")]
-        let pid = pid as i32;
+        Self::start_with_options(Target::Process(pid), event_tx.into(), event_filter.into()).await
+    }
 
-        Ok(Self(
-            PlatformWindowObserver::start(pid, event_tx, event_filter).await?,
-        ))
+    pub(crate) async fn start_with_options(
+        target: Target,
+        event_tx: EventSender,
+        options: ObserverOptions,
+    ) -> Result<Self, Error> {
+        let discarded = event_tx.discard_counter();
+        let inner = match target {
+            Target::Process(pid) => {
+                #[cfg(target_os = "macos")]
+                let pid = pid as i32;
+
+                PlatformWindowObserver::start_with_options(pid, event_tx, options).await?
+            }
+            #[cfg(any(target_os = "windows", feature = "macos-private-api"))]
+            Target::Window(window_id) => {
+                PlatformWindowObserver::start_for_window_with_options(
+                    window_id.into_platform_window_id(),
+                    event_tx,
+                    options,
+                )
+                .await?
+            }
+        };
+
+        Ok(Self { inner, discarded })
+    }
+
+    /// Same as [`start`](Self::start), but it blocks the current thread instead of being async.
+    /// It can be used without any async runtime.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use window_observer::{EventFilter, WindowObserver, channel};
+    ///
+    /// # fn example(pid: u32) -> Result<(), window_observer::Error> {
+    /// let (event_tx, mut event_rx) = channel::unbounded();
+    /// let observer = WindowObserver::start_blocking(pid, event_tx, EventFilter::all())?;
+    ///
+    /// while let Ok(event) = event_rx.recv_timeout(Duration::from_secs(10)) {
+    ///     println!("{event:?}");
+    /// }
+    ///
+    /// observer.stop_blocking()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn start_blocking(
+        pid: u32,
+        event_tx: impl Into<EventSender>,
+        event_filter: EventFilter,
+    ) -> Result<Self, Error> {
+        executor::block_on(Self::start(pid, event_tx, event_filter))
+    }
+
+    /// Creates a new [`WindowObserver`] for a given process ID and start the observer.
+    /// The events are received from the returned [`EventStream`],
+    /// and dropping the stream stops the observer.
+    pub async fn start_stream(pid: u32, event_filter: EventFilter) -> Result<EventStream, Error> {
+        let (event_tx, event_rx) = channel::unbounded();
+        let observer = Self::start(pid, event_tx, event_filter).await?;
+
+        Ok(EventStream::new(event_rx, observer))
+    }
+
+    /// Creates a new [`WindowObserver`] that observes only the window with the given ID
+    /// and start the observer.
+    ///
+    /// Events of the other windows of the process are not sent.
+    /// After [`Event::Closed`] of the window, the observer drops `event_tx`,
+    /// so the channel is closed unless there are other senders.
+    /// [`Event::Created`] is never sent because the window already exists.
+    ///
+    /// # Platform-specific
+    /// - **macOS:** The notifications about the window itself are subscribed
+    ///   to the window element instead of the whole application.
+    ///   **Warning:** It uses the private API `_AXUIElementGetWindow` of macOS.
+    /// - **Windows:** The events of the other windows are discarded by the interpreter
+    ///   before a [`Window`] is created for them.
+    #[cfg(any(target_os = "windows", feature = "macos-private-api"))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(target_os = "windows", feature = "macos-private-api")))
+    )]
+    pub async fn start_for_window(
+        window_id: window_getter::WindowId,
+        event_tx: impl Into<EventSender>,
+        event_filter: EventFilter,
+    ) -> Result<Self, Error> {
+        Self::start_with_options(
+            Target::Window(window_id),
+            event_tx.into(),
+            event_filter.into(),
+        )
+        .await
     }
 
     /// Stops the observer and cleans up resources.
     ///
+    /// The events that the observer has received are sent before it returns,
+    /// followed by [`ObserverEvent::Stopped`] if [`EventFilter::lifecycle`] is enabled.
+    ///
     /// # Notes
     /// If you don't call this method, the observer will continue to run until droped.
     ///
@@ -195,15 +876,98 @@ impl WindowObserver {
     /// - **macOS:** It will always return [`Ok`].
     pub async fn stop(self) -> Result<(), Error> {
         #[cfg(target_os = "macos")]
-        self.0.stop().await;
+        self.inner.stop().await;
         #[cfg(target_os = "windows")]
-        self.0.stop().await?;
+        self.inner.stop().await?;
 
         Ok(())
     }
 
+    /// Same as [`stop`](Self::stop), but it blocks the current thread instead of being async.
+    pub fn stop_blocking(self) -> Result<(), Error> {
+        executor::block_on(self.stop())
+    }
+
+    /// Returns `false` if the backend has stopped receiving events of the OS by itself,
+    /// which is reported by [`DegradedReason::BackendEnded`].
+    /// [`SupervisedWindowObserver`] restarts such an observer.
+    pub fn is_running(&self) -> bool {
+        self.inner.is_running()
+    }
+
+    /// Returns the number of events discarded by the channel of [`channel`]
+    /// that the observer sends events to.
+    /// It is `None` if the events are sent to another [`EventSink`].
+    pub fn discarded(&self) -> Option<DiscardedEvents> {
+        self.discarded.as_ref().map(channel::DiscardCounter::get)
+    }
+
     /// Returns underlying platform-specific observer.
     pub fn inner(&self) -> &PlatformWindowObserver {
-        &self.0
+        &self.inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_filter_round_trips_through_display() {
+        let filters = [
+            EventFilter::empty(),
+            EventFilter::all(),
+            EventFilter {
+                moved: true,
+                title_changed: true,
+                display_changed: true,
+                ..EventFilter::empty()
+            },
+        ];
+
+        for filter in filters {
+            assert_eq!(filter.to_string().parse::<EventFilter>(), Ok(filter));
+        }
+
+        for name in EVENT_NAMES {
+            let filter: EventFilter = name.parse().unwrap();
+            assert_eq!(filter.to_string(), name);
+        }
+    }
+
+    #[test]
+    fn event_filter_parses_all() {
+        assert_eq!("all".parse::<EventFilter>(), Ok(EventFilter::all()));
+        assert_eq!("moved, all".parse::<EventFilter>(), Ok(EventFilter::all()));
+    }
+
+    #[test]
+    fn event_filter_rejects_unknown_names() {
+        assert_eq!(
+            "moved,teleported".parse::<EventFilter>(),
+            Err(ParseEventFilterError("teleported".to_owned()))
+        );
+        // The names are case-sensitive like the fields.
+        assert_eq!(
+            "Moved".parse::<EventFilter>(),
+            Err(ParseEventFilterError("Moved".to_owned()))
+        );
+    }
+
+    #[test]
+    fn event_filter_ignores_whitespace_and_empty_names() {
+        let filter: EventFilter = " moved ,\tresized ,, ".parse().unwrap();
+        assert_eq!(
+            filter,
+            EventFilter {
+                moved: true,
+                resized: true,
+                ..EventFilter::empty()
+            }
+        );
+
+        assert_eq!("".parse::<EventFilter>(), Ok(EventFilter::empty()));
+        assert_eq!(" , ".parse::<EventFilter>(), Ok(EventFilter::empty()));
+        assert_eq!(EventFilter::empty().to_string(), "");
     }
 }
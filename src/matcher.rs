@@ -0,0 +1,484 @@
+//! Selection of the windows whose events are sent.
+//!
+//! [`EventFilter`](crate::EventFilter) selects the kinds of the events, and [`WindowMatcher`]
+//! selects the windows. The matcher is evaluated by the backend before the event is sent,
+//! so the events of the other windows never reach the channel.
+
+use std::ops::Not;
+
+use crate::{Monitor, MonitorId, Rect, Size, Window};
+
+/// The kind of a window.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum WindowRole {
+    /// An ordinary window of an application.
+    Standard,
+    /// A dialog such as an alert or a file picker.
+    Dialog,
+    /// A floating panel such as a tool palette or an inspector.
+    Panel,
+    /// Any other window such as a menu, a tooltip or a splash screen.
+    Other,
+}
+
+/// The properties of a window that a [`WindowMatcher`] inspects.
+///
+/// Each property is retrieved only when a matcher needs it,
+/// and `None` means that it is not available.
+pub trait WindowProperties {
+    /// Returns the title of the window.
+    fn title(&self) -> Option<String>;
+
+    /// Returns the identifier of the application that owns the window.
+    ///
+    /// # Platform-specific
+    /// - **macOS:** It is the name of the application such as `TextEdit`.
+    /// - **Windows:** It is the file name of the executable such as `notepad.exe`.
+    fn app_id(&self) -> Option<String>;
+
+    /// Returns the kind of the window.
+    ///
+    /// # Platform-specific
+    /// - **macOS:** It is told from the subrole of the window in the Accessibility API.
+    /// - **Windows:** It is told from the styles and the class of the window.
+    fn role(&self) -> Option<WindowRole>;
+
+    /// Returns the bounds of the window.
+    fn bounds(&self) -> Option<Rect>;
+
+    /// Returns the display that shows the largest part of the window.
+    fn monitor(&self) -> Option<MonitorId> {
+        Monitor::dominant_for(&self.bounds()?).map(|monitor| monitor.id)
+    }
+}
+
+impl WindowProperties for Window {
+    fn title(&self) -> Option<String> {
+        WindowProperties::title(&self.0)
+    }
+
+    fn app_id(&self) -> Option<String> {
+        WindowProperties::app_id(&self.0)
+    }
+
+    fn role(&self) -> Option<WindowRole> {
+        WindowProperties::role(&self.0)
+    }
+
+    fn bounds(&self) -> Option<Rect> {
+        WindowProperties::bounds(&self.0)
+    }
+}
+
+/// A pattern of a text such as a title.
+#[derive(Debug, Clone)]
+pub enum TextPattern {
+    /// Matches the text that is equal to the string.
+    Exact(String),
+    /// Matches the text with a glob, where `*` matches any characters
+    /// and `?` matches a single character.
+    Glob(String),
+    /// Matches the text that contains a match of the regular expression.
+    #[cfg(feature = "regex")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+    Regex(regex::Regex),
+}
+
+impl TextPattern {
+    /// Creates a [`TextPattern::Exact`].
+    pub fn exact(text: impl Into<String>) -> Self {
+        Self::Exact(text.into())
+    }
+
+    /// Creates a [`TextPattern::Glob`].
+    pub fn glob(pattern: impl Into<String>) -> Self {
+        Self::Glob(pattern.into())
+    }
+
+    /// Creates a [`TextPattern::Regex`]. It fails if the regular expression is invalid.
+    #[cfg(feature = "regex")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+    pub fn regex(pattern: &str) -> Result<Self, regex::Error> {
+        Ok(Self::Regex(regex::Regex::new(pattern)?))
+    }
+
+    /// Checks whether the text matches the pattern.
+    pub fn matches(&self, text: &str) -> bool {
+        match self {
+            Self::Exact(expected) => expected == text,
+            Self::Glob(pattern) => glob_matches(pattern, text),
+            #[cfg(feature = "regex")]
+            Self::Regex(regex) => regex.is_match(text),
+        }
+    }
+}
+
+/// Matches the text with the glob pattern by backtracking to the last `*`.
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // The position of the last `*` in the pattern and the text that it has matched up to.
+    let mut star = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    // Let the `*` match one more character.
+                    star = Some((star_p, star_t + 1));
+                    p = star_p + 1;
+                    t = star_t + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Selects windows by their properties. It is attached to an observer by
+/// [`WindowObserverBuilder::matcher`](crate::WindowObserverBuilder::matcher).
+///
+/// A matcher doesn't match a window whose property it needs is not available,
+/// for example because the window was closed meanwhile.
+///
+/// # Example
+/// ```
+/// use window_observer::{Size, matcher::{TextPattern, WindowMatcher, WindowRole}};
+///
+/// // Standard windows whose title ends with ".txt", except for the ones smaller than 50x50.
+/// let matcher = WindowMatcher::role(WindowRole::Standard)
+///     .and(WindowMatcher::title(TextPattern::glob("*.txt")))
+///     .and(WindowMatcher::min_size(Size { width: 50., height: 50. }));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub enum WindowMatcher {
+    /// Matches any window.
+    #[default]
+    Any,
+    /// Matches the windows whose title matches the pattern.
+    Title(TextPattern),
+    /// Matches the windows whose application matches the pattern.
+    /// See [`WindowProperties::app_id`] for the identifier.
+    AppId(TextPattern),
+    /// Matches the windows of the role.
+    Role(WindowRole),
+    /// Matches the windows whose width and height are within the bounds, inclusive.
+    Size {
+        min: Option<Size>,
+        max: Option<Size>,
+    },
+    /// Matches the windows that are mostly on the display.
+    Monitor(MonitorId),
+    /// Matches the windows that all matchers match. It matches any window if it is empty.
+    All(Vec<WindowMatcher>),
+    /// Matches the windows that any of the matchers match. It matches no window if it is empty.
+    AnyOf(Vec<WindowMatcher>),
+    /// Matches the windows that the matcher doesn't match.
+    Not(Box<WindowMatcher>),
+}
+
+impl WindowMatcher {
+    /// Creates a [`WindowMatcher::Title`].
+    pub fn title(pattern: TextPattern) -> Self {
+        Self::Title(pattern)
+    }
+
+    /// Creates a [`WindowMatcher::AppId`].
+    pub fn app_id(pattern: TextPattern) -> Self {
+        Self::AppId(pattern)
+    }
+
+    /// Creates a [`WindowMatcher::Role`].
+    pub fn role(role: WindowRole) -> Self {
+        Self::Role(role)
+    }
+
+    /// Creates a [`WindowMatcher::Size`] that matches the windows at least as large as `min`.
+    pub fn min_size(min: Size) -> Self {
+        Self::Size {
+            min: Some(min),
+            max: None,
+        }
+    }
+
+    /// Creates a [`WindowMatcher::Size`] that matches the windows at most as large as `max`.
+    pub fn max_size(max: Size) -> Self {
+        Self::Size {
+            min: None,
+            max: Some(max),
+        }
+    }
+
+    /// Creates a [`WindowMatcher::Monitor`].
+    pub fn monitor(id: MonitorId) -> Self {
+        Self::Monitor(id)
+    }
+
+    /// Combines the matchers so that both of them must match.
+    pub fn and(self, other: Self) -> Self {
+        match self {
+            Self::All(mut matchers) => {
+                matchers.push(other);
+                Self::All(matchers)
+            }
+            matcher => Self::All(vec![matcher, other]),
+        }
+    }
+
+    /// Combines the matchers so that either of them must match.
+    pub fn or(self, other: Self) -> Self {
+        match self {
+            Self::AnyOf(mut matchers) => {
+                matchers.push(other);
+                Self::AnyOf(matchers)
+            }
+            matcher => Self::AnyOf(vec![matcher, other]),
+        }
+    }
+
+    /// Checks whether the window matches.
+    pub fn matches(&self, window: &(impl WindowProperties + ?Sized)) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Title(pattern) => window.title().is_some_and(|title| pattern.matches(&title)),
+            Self::AppId(pattern) => window.app_id().is_some_and(|id| pattern.matches(&id)),
+            Self::Role(role) => window.role() == Some(*role),
+            Self::Size { min, max } => window.bounds().is_some_and(|bounds| {
+                let size = bounds.size();
+
+                min.is_none_or(|min| size.width >= min.width && size.height >= min.height)
+                    && max.is_none_or(|max| size.width <= max.width && size.height <= max.height)
+            }),
+            Self::Monitor(id) => window.monitor() == Some(*id),
+            Self::All(matchers) => matchers.iter().all(|matcher| matcher.matches(window)),
+            Self::AnyOf(matchers) => matchers.iter().any(|matcher| matcher.matches(window)),
+            Self::Not(matcher) => !matcher.matches(window),
+        }
+    }
+}
+
+impl Not for WindowMatcher {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        match self {
+            Self::Not(matcher) => *matcher,
+            matcher => Self::Not(Box::new(matcher)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A window whose properties are given by the test.
+    #[derive(Default)]
+    struct FakeWindow {
+        title: Option<&'static str>,
+        app_id: Option<&'static str>,
+        role: Option<WindowRole>,
+        size: Option<(f64, f64)>,
+        monitor: Option<MonitorId>,
+    }
+
+    impl WindowProperties for FakeWindow {
+        fn title(&self) -> Option<String> {
+            self.title.map(str::to_owned)
+        }
+
+        fn app_id(&self) -> Option<String> {
+            self.app_id.map(str::to_owned)
+        }
+
+        fn role(&self) -> Option<WindowRole> {
+            self.role
+        }
+
+        fn bounds(&self) -> Option<Rect> {
+            self.size.map(|(width, height)| Rect {
+                x: 0.,
+                y: 0.,
+                width,
+                height,
+            })
+        }
+
+        fn monitor(&self) -> Option<MonitorId> {
+            self.monitor
+        }
+    }
+
+    fn titled(title: &'static str) -> FakeWindow {
+        FakeWindow {
+            title: Some(title),
+            ..Default::default()
+        }
+    }
+
+    fn sized(width: f64, height: f64) -> FakeWindow {
+        FakeWindow {
+            size: Some((width, height)),
+            ..Default::default()
+        }
+    }
+
+    fn size(width: f64, height: f64) -> Size {
+        Size { width, height }
+    }
+
+    #[test]
+    fn glob_star_matches_any_characters() {
+        assert!(glob_matches("*", ""));
+        assert!(glob_matches("*", "anything"));
+        assert!(glob_matches("*.txt", "notes.txt"));
+        assert!(glob_matches("*.txt", ".txt"));
+        assert!(!glob_matches("*.txt", "notes.txt.bak"));
+        assert!(glob_matches("a*b*c", "aXbYbZc"));
+        assert!(glob_matches("**a", "a"));
+        assert!(!glob_matches("a*", "ba"));
+    }
+
+    #[test]
+    fn glob_backtracks_to_the_last_star() {
+        assert!(glob_matches("*ab", "aab"));
+        assert!(glob_matches("*a*b", "acacb"));
+        assert!(!glob_matches("*a*b", "acac"));
+    }
+
+    #[test]
+    fn glob_question_mark_matches_a_single_character() {
+        assert!(glob_matches("?", "a"));
+        assert!(!glob_matches("?", ""));
+        assert!(!glob_matches("?", "ab"));
+        assert!(glob_matches("file?.txt", "file1.txt"));
+        assert!(glob_matches("?*", "a"));
+        assert!(!glob_matches("?*", ""));
+        // It matches a character, not a byte.
+        assert!(glob_matches("??", "日本"));
+    }
+
+    #[test]
+    fn glob_without_wildcards_matches_exactly() {
+        assert!(glob_matches("", ""));
+        assert!(!glob_matches("", "a"));
+        assert!(glob_matches("abc", "abc"));
+        assert!(!glob_matches("abc", "abcd"));
+        assert!(!glob_matches("abc", "ABC"));
+    }
+
+    #[test]
+    fn text_patterns() {
+        assert!(TextPattern::exact("a*").matches("a*"));
+        assert!(!TextPattern::exact("a*").matches("ab"));
+        assert!(TextPattern::glob("a*").matches("ab"));
+    }
+
+    #[test]
+    fn empty_combinators() {
+        let window = titled("a");
+
+        assert!(WindowMatcher::All(Vec::new()).matches(&window));
+        assert!(!WindowMatcher::AnyOf(Vec::new()).matches(&window));
+    }
+
+    #[test]
+    fn combinators() {
+        let window = titled("notes.txt");
+        let txt = || WindowMatcher::title(TextPattern::glob("*.txt"));
+        let md = || WindowMatcher::title(TextPattern::glob("*.md"));
+
+        assert!(txt().and(WindowMatcher::Any).matches(&window));
+        assert!(!txt().and(md()).matches(&window));
+        assert!(md().or(txt()).matches(&window));
+        assert!(!md().or(md()).matches(&window));
+        assert!(matches!(
+            txt().and(md()).and(txt()),
+            WindowMatcher::All(matchers) if matchers.len() == 3
+        ));
+    }
+
+    #[test]
+    fn not_negates_the_matcher() {
+        let window = titled("a");
+        let matcher = WindowMatcher::title(TextPattern::exact("a"));
+
+        assert!(!(!matcher.clone()).matches(&window));
+        assert!((!!matcher.clone()).matches(&window));
+        assert!(matches!(!!matcher, WindowMatcher::Title(_)));
+        assert!(WindowMatcher::Not(Box::new(WindowMatcher::AnyOf(Vec::new()))).matches(&window));
+    }
+
+    #[test]
+    fn unavailable_properties_never_match() {
+        let window = FakeWindow::default();
+
+        assert!(!WindowMatcher::title(TextPattern::glob("*")).matches(&window));
+        assert!(!WindowMatcher::app_id(TextPattern::glob("*")).matches(&window));
+        assert!(!WindowMatcher::role(WindowRole::Standard).matches(&window));
+        assert!(!WindowMatcher::min_size(size(0., 0.)).matches(&window));
+        assert!(!WindowMatcher::monitor(MonitorId(1)).matches(&window));
+        // The negation of a matcher that needs the property matches the window.
+        assert!((!WindowMatcher::role(WindowRole::Standard)).matches(&window));
+    }
+
+    #[test]
+    fn size_bounds_are_inclusive() {
+        let matcher = WindowMatcher::Size {
+            min: Some(size(100., 50.)),
+            max: Some(size(200., 100.)),
+        };
+
+        assert!(matcher.matches(&sized(100., 50.)));
+        assert!(matcher.matches(&sized(200., 100.)));
+        assert!(matcher.matches(&sized(150., 75.)));
+        assert!(!matcher.matches(&sized(99., 75.)));
+        assert!(!matcher.matches(&sized(150., 49.)));
+        assert!(!matcher.matches(&sized(201., 75.)));
+        assert!(!matcher.matches(&sized(150., 101.)));
+    }
+
+    #[test]
+    fn size_needs_both_dimensions() {
+        assert!(!WindowMatcher::min_size(size(100., 100.)).matches(&sized(200., 50.)));
+        assert!(!WindowMatcher::max_size(size(100., 100.)).matches(&sized(50., 200.)));
+        assert!(
+            WindowMatcher::Size {
+                min: None,
+                max: None
+            }
+            .matches(&sized(0., 0.))
+        );
+    }
+
+    #[test]
+    fn properties_of_the_window() {
+        let window = FakeWindow {
+            app_id: Some("notepad.exe"),
+            role: Some(WindowRole::Dialog),
+            monitor: Some(MonitorId(2)),
+            ..Default::default()
+        };
+
+        assert!(WindowMatcher::app_id(TextPattern::exact("notepad.exe")).matches(&window));
+        assert!(WindowMatcher::role(WindowRole::Dialog).matches(&window));
+        assert!(!WindowMatcher::role(WindowRole::Standard).matches(&window));
+        assert!(WindowMatcher::monitor(MonitorId(2)).matches(&window));
+        assert!(!WindowMatcher::monitor(MonitorId(1)).matches(&window));
+    }
+}
@@ -0,0 +1,190 @@
+//! Displays that windows are shown on.
+
+use std::time::{Duration, Instant};
+
+use crate::{
+    Event,
+    window::{Position, Rect},
+};
+
+/// The identifier of a [`Monitor`].
+///
+/// # Platform-specific
+/// - **macOS:** It is a [`CGDirectDisplayID`][CGDirectDisplayID].
+/// - **Windows:** It is the address of an `HMONITOR`.
+///   It is valid until the displays are reconfigured.
+///
+/// [CGDirectDisplayID]: https://developer.apple.com/documentation/coregraphics/cgdirectdisplayid?language=objc
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct MonitorId(pub(crate) u64);
+
+impl MonitorId {
+    /// Returns the platform-specific value of the identifier.
+    pub fn as_raw(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Represents a display. The coordinates are in [`CoordinateSpace::native`](crate::CoordinateSpace::native).
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Monitor {
+    /// The identifier of the display.
+    pub id: MonitorId,
+    /// The name of the display.
+    ///
+    /// # Platform-specific
+    /// - **macOS:** It is always `None` because the name is only available from AppKit
+    ///   on the main thread.
+    /// - **Windows:** It is the name of the device such as `\\.\DISPLAY1`.
+    pub name: Option<String>,
+    /// The bounds of the whole display.
+    pub bounds: Rect,
+    /// The bounds of the display without the taskbar, the menu bar and the Dock.
+    ///
+    /// # Platform-specific
+    /// - **macOS:** It is always same as [`bounds`](Self::bounds) for the same reason as
+    ///   [`name`](Self::name).
+    pub work_area: Rect,
+    /// The number of physical pixels per logical pixel of the display.
+    pub scale_factor: f64,
+    /// Whether the display is the primary display, whose top-left corner is the origin.
+    pub is_primary: bool,
+}
+
+impl Monitor {
+    /// Returns all displays that are currently active.
+    pub fn all() -> Vec<Monitor> {
+        #[cfg(target_os = "macos")]
+        {
+            crate::platform_impl::macos::display::monitors()
+        }
+        #[cfg(target_os = "windows")]
+        {
+            crate::platform_impl::windows::monitor::monitors()
+        }
+    }
+
+    /// Returns the primary display.
+    pub fn primary() -> Option<Monitor> {
+        Self::all().into_iter().find(|monitor| monitor.is_primary)
+    }
+
+    /// Returns the display that shows the largest part of the rectangle.
+    /// If the rectangle is on no display, the display nearest to its center is returned.
+    pub fn dominant_for(bounds: &Rect) -> Option<Monitor> {
+        dominant(Self::all(), bounds)
+    }
+
+    /// Returns the display that contains the point.
+    pub fn at(point: Position) -> Option<Monitor> {
+        Self::all()
+            .into_iter()
+            .find(|monitor| monitor.bounds.contains(point))
+    }
+}
+
+/// Chooses the display that shows the largest part of the rectangle among the given displays.
+pub(crate) fn dominant(monitors: Vec<Monitor>, bounds: &Rect) -> Option<Monitor> {
+    let overlap = |monitor: &Monitor| {
+        monitor
+            .bounds
+            .intersection(bounds)
+            .map_or(0., |intersection| intersection.area())
+    };
+
+    let largest = monitors
+        .iter()
+        .max_by(|a, b| overlap(a).total_cmp(&overlap(b)))
+        .filter(|monitor| overlap(monitor) > 0.);
+
+    match largest {
+        Some(monitor) => Some(monitor.clone()),
+        None => {
+            let center = bounds.center();
+
+            monitors.into_iter().min_by(|a, b| {
+                a.bounds
+                    .distance_to(center)
+                    .total_cmp(&b.bounds.distance_to(center))
+            })
+        }
+    }
+}
+
+/// The minimum interval between the checks of the displays that are not forced.
+const DISPLAY_CHECK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Detects the changes of the displays by comparing them with the last known ones.
+///
+/// The interpreters force a check before they dispatch the geometry events of windows,
+/// so the display events precede the events of the windows moved by the change.
+#[derive(Debug)]
+pub(crate) struct DisplayTracker {
+    monitors: Vec<Monitor>,
+    last_check: Instant,
+}
+
+impl DisplayTracker {
+    pub fn new() -> Self {
+        Self {
+            monitors: Monitor::all(),
+            last_check: Instant::now(),
+        }
+    }
+
+    /// Returns the events of the changes since the last check.
+    /// Unless `force` is `true`, the displays are checked only if the interval has elapsed.
+    pub fn check(&mut self, force: bool) -> Vec<Event> {
+        if !force && self.last_check.elapsed() < DISPLAY_CHECK_INTERVAL {
+            return Vec::new();
+        }
+        self.last_check = Instant::now();
+
+        let current = Monitor::all();
+        let mut previous = std::mem::replace(&mut self.monitors, current.clone());
+        let mut events = Vec::new();
+        let mut added = Vec::new();
+
+        for monitor in current {
+            // The identifier can change when the displays are reconfigured on Windows,
+            // so the name identifies the display in that case.
+            let index = previous
+                .iter()
+                .position(|known| known.id == monitor.id)
+                .or_else(|| {
+                    previous
+                        .iter()
+                        .position(|known| known.name.is_some() && known.name == monitor.name)
+                });
+
+            match index {
+                Some(index) => {
+                    let known = previous.swap_remove(index);
+
+                    if known != monitor {
+                        events.push(Event::DisplayChanged {
+                            from: Box::new(known),
+                            to: Box::new(monitor),
+                        });
+                    }
+                }
+                None => added.push(monitor),
+            }
+        }
+
+        events.extend(previous.into_iter().map(|monitor| Event::DisplayRemoved {
+            monitor: Box::new(monitor),
+        }));
+        events.extend(added.into_iter().map(|monitor| Event::DisplayAdded {
+            monitor: Box::new(monitor),
+        }));
+
+        events
+    }
+}
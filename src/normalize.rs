@@ -0,0 +1,72 @@
+//! Conversion of the coordinates in the events into a coordinate space.
+
+use crate::{
+    CoordinateSpace, Event, EventResult, EventSender, MaybeWindowAvailable, Monitor, Rect,
+    sink::EventSink,
+};
+
+/// Converts the bounds in the events from [`CoordinateSpace::native`] into a coordinate space
+/// before they are sent to the next sink. See [`WindowObserverBuilder::coordinate_space`](crate::WindowObserverBuilder::coordinate_space).
+pub(crate) struct NormalizingSink {
+    space: CoordinateSpace,
+    event_tx: EventSender,
+}
+
+impl NormalizingSink {
+    pub fn new(space: CoordinateSpace, event_tx: EventSender) -> Self {
+        Self { space, event_tx }
+    }
+
+    /// Converts the bounds of a window with the scale factor of the display that shows it.
+    fn convert_bounds(&self, bounds: &mut Rect) {
+        if let Some(monitor) = Monitor::dominant_for(bounds) {
+            *bounds = bounds.convert(CoordinateSpace::native(), self.space, monitor.scale_factor);
+        }
+    }
+
+    /// Converts the bounds of a display with its own scale factor.
+    fn convert_monitor(&self, monitor: &mut Monitor) {
+        let native = CoordinateSpace::native();
+
+        monitor.bounds = monitor
+            .bounds
+            .convert(native, self.space, monitor.scale_factor);
+        monitor.work_area = monitor
+            .work_area
+            .convert(native, self.space, monitor.scale_factor);
+    }
+
+    fn convert_event(&self, event: &mut Event) {
+        match event {
+            Event::Moved { bounds }
+            | Event::Resized { bounds }
+            | Event::MoveEnded { bounds }
+            | Event::ResizeEnded { bounds } => {
+                self.convert_bounds(bounds);
+            }
+            Event::MonitorChanged { from, to } | Event::DisplayChanged { from, to } => {
+                self.convert_monitor(from);
+                self.convert_monitor(to);
+            }
+            Event::DisplayAdded { monitor } | Event::DisplayRemoved { monitor } => {
+                self.convert_monitor(monitor);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl EventSink for NormalizingSink {
+    fn send(&mut self, mut result: EventResult) -> bool {
+        if let Ok(
+            MaybeWindowAvailable::Available { event, .. }
+            | MaybeWindowAvailable::NotAvailable { event }
+            | MaybeWindowAvailable::InitialState { event, .. },
+        ) = &mut result
+        {
+            self.convert_event(event);
+        }
+
+        self.event_tx.send(result)
+    }
+}
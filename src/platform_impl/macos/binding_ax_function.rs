@@ -4,7 +4,8 @@
 use accessibility::AXUIElement;
 use accessibility_sys::{AXError, AXValueGetValue, AXValueRef};
 use core_foundation::{
-    base::{CFTypeRef, TCFType},
+    array::{CFArray, CFArrayRef},
+    base::{CFType, CFTypeRef, TCFType},
     boolean::CFBoolean,
     dictionary::CFDictionary,
     string::CFString,
@@ -36,6 +37,63 @@ pub fn ax_ui_element_copy_attribute_value(
     .into_result(value)
 }
 
+/// Copies the values of multiple attributes from an [`AXUIElement`] in a single query.
+///
+/// # Parameters
+/// - `element`: The `AXUIElement` to query.
+/// - `attributes`: The names of the attributes to retrieve.
+///
+/// # Returns
+/// A [`Result`] containing the values in the same order as `attributes` or an [`AXError`].
+/// A value that could not be retrieved is an [`AXValue`][ax_value] of the error.
+///
+/// [ax_value]: https://developer.apple.com/documentation/applicationservices/axvalue_h?language=objc
+pub fn ax_ui_element_copy_multiple_attribute_values(
+    element: &AXUIElement,
+    attributes: &[&str],
+) -> Result<CFArray<CFType>, AXError> {
+    let attributes = attributes
+        .iter()
+        .map(|attribute| CFString::new(attribute))
+        .collect::<Vec<_>>();
+    let attributes = CFArray::from_CFTypes(&attributes);
+    let mut values: CFArrayRef = std::ptr::null();
+
+    unsafe {
+        accessibility_sys::AXUIElementCopyMultipleAttributeValues(
+            element.as_concrete_TypeRef(),
+            attributes.as_concrete_TypeRef(),
+            0,
+            &mut values,
+        )
+    }
+    .into_result(())?;
+
+    Ok(unsafe { CFArray::wrap_under_create_rule(values) })
+}
+
+/// Extracts the value of an [`AXValue`][ax_value] that is an item of an array.
+///
+/// # Returns
+/// The value, or the [`AXError`] that the item represents instead of the value.
+///
+/// [ax_value]: https://developer.apple.com/documentation/applicationservices/axvalue_h?language=objc
+pub fn ax_value_get_typed_value<T>(
+    value: &CFType,
+    r#type: accessibility_sys::AXValueType,
+) -> Result<T, AXError> {
+    if value.type_of() != unsafe { accessibility_sys::AXValueGetTypeID() } {
+        return Err(accessibility_sys::kAXErrorIllegalArgument);
+    }
+
+    let value = value.as_CFTypeRef() as AXValueRef;
+
+    unsafe { ax_value_get_value::<T>(value, r#type) }.ok_or_else(|| {
+        unsafe { ax_value_get_value::<AXError>(value, accessibility_sys::kAXValueTypeAXError) }
+            .unwrap_or(accessibility_sys::kAXErrorFailure)
+    })
+}
+
 /// Utility function for [`AXValueGetValue`].
 ///
 /// # Safety
@@ -64,6 +122,33 @@ pub unsafe fn ax_value_get_value<T>(
     }
 }
 
+/// Utility function for [`AXValueCreate`][accessibility_sys::AXValueCreate].
+///
+/// # Safety
+/// The type of `value` must be the one represented by `type`.
+///
+/// # Returns
+/// An [`Option`] containing the created value if successful.
+pub unsafe fn ax_value_create<T>(
+    r#type: accessibility_sys::AXValueType,
+    value: &T,
+) -> Option<CFType> {
+    let value = unsafe { accessibility_sys::AXValueCreate(r#type, value as *const T as _) };
+
+    (!value.is_null()).then(|| unsafe { CFType::wrap_under_create_rule(value as CFTypeRef) })
+}
+
+/// Retrieves the ID of the process that an [`AXUIElement`] belongs to.
+///
+/// # Returns
+/// A [`Result`] containing the process ID or an [`AXError`].
+pub fn ax_ui_element_get_pid(element: &AXUIElement) -> Result<libc::pid_t, AXError> {
+    let mut pid = 0;
+
+    unsafe { accessibility_sys::AXUIElementGetPid(element.as_concrete_TypeRef(), &mut pid) }
+        .into_result(pid)
+}
+
 /// Checks if the current process is trusted for accessibility features.
 pub fn ax_is_process_trusted() -> bool {
     unsafe { accessibility_sys::AXIsProcessTrusted() }
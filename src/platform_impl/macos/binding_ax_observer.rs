@@ -33,7 +33,11 @@ extern "C" fn observer_callback(
     let element = unsafe { AXUIElement::wrap_under_get_rule(element as _) };
 
     if let Ok(mut refcon) = refcon.lock() {
-        (refcon.callback)(element, notification.to_string());
+        // A panic must not unwind into the thread of `CFRunLoop` or poison the lock,
+        // otherwise the observer would stop receiving notifications.
+        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            (refcon.callback)(element, notification.to_string());
+        }));
     };
 }
 
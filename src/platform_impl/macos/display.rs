@@ -0,0 +1,76 @@
+//! Queries of the displays through Core Graphics.
+
+use objc2_core_foundation::CGPoint;
+use objc2_core_graphics::{
+    CGDirectDisplayID, CGDisplayBounds, CGDisplayCopyDisplayMode, CGDisplayMode,
+    CGGetActiveDisplayList, CGGetDisplaysWithPoint, CGMainDisplayID,
+};
+
+use crate::{
+    monitor::{Monitor, MonitorId},
+    window::{Position, Rect},
+};
+
+/// The maximum number of displays that are enumerated.
+const MAX_DISPLAYS: usize = 32;
+
+/// Returns the active displays.
+pub(crate) fn monitors() -> Vec<Monitor> {
+    let mut displays = [0; MAX_DISPLAYS];
+    let mut count = 0;
+
+    unsafe {
+        CGGetActiveDisplayList(MAX_DISPLAYS as u32, displays.as_mut_ptr(), &mut count);
+    }
+
+    let main = CGMainDisplayID();
+
+    displays[..count as usize]
+        .iter()
+        .map(|&display| {
+            let bounds = CGDisplayBounds(display);
+            let bounds = Rect::new(bounds.origin.into(), bounds.size.into());
+
+            Monitor {
+                id: MonitorId(display as u64),
+                name: None,
+                bounds,
+                work_area: bounds,
+                scale_factor: scale_factor(display),
+                is_primary: display == main,
+            }
+        })
+        .collect()
+}
+
+/// Returns the display that contains the point, or the main display if there is none.
+pub(crate) fn display_at(point: Position) -> CGDirectDisplayID {
+    let mut display = 0;
+    let mut count = 0;
+
+    unsafe {
+        CGGetDisplaysWithPoint(CGPoint::new(point.x, point.y), 1, &mut display, &mut count);
+    }
+
+    if count == 0 {
+        CGMainDisplayID()
+    } else {
+        display
+    }
+}
+
+/// Returns the number of pixels per point of the display.
+pub(crate) fn scale_factor(display: CGDirectDisplayID) -> f64 {
+    let Some(mode) = CGDisplayCopyDisplayMode(display) else {
+        return 1.;
+    };
+
+    let width = CGDisplayMode::width(Some(&mode));
+    let pixel_width = CGDisplayMode::pixel_width(Some(&mode));
+
+    if width == 0 {
+        1.
+    } else {
+        pixel_width as f64 / width as f64
+    }
+}
@@ -1,8 +1,36 @@
 use accessibility::Error;
 use accessibility_sys::AXError;
 
+use crate::ErrorKind;
+
 pub type MacOSError = Error;
 
+/// Classifies the error of the Accessibility API.
+pub(crate) fn error_kind(error: &MacOSError) -> ErrorKind {
+    match error {
+        Error::NotFound => ErrorKind::WindowGone,
+        Error::UnexpectedType { .. } => ErrorKind::Unsupported,
+        Error::Ax(ax_error) => match *ax_error {
+            accessibility_sys::kAXErrorInvalidUIElement
+            | accessibility_sys::kAXErrorInvalidUIElementObserver => ErrorKind::WindowGone,
+            accessibility_sys::kAXErrorAPIDisabled => ErrorKind::PermissionDenied,
+            // The application is busy or not responding.
+            accessibility_sys::kAXErrorCannotComplete | accessibility_sys::kAXErrorNoValue => {
+                ErrorKind::Transient
+            }
+            accessibility_sys::kAXErrorNotImplemented
+            | accessibility_sys::kAXErrorAttributeUnsupported
+            | accessibility_sys::kAXErrorActionUnsupported
+            | accessibility_sys::kAXErrorNotificationUnsupported
+            | accessibility_sys::kAXErrorParameterizedAttributeUnsupported => {
+                ErrorKind::Unsupported
+            }
+            _ => ErrorKind::Fatal,
+        },
+        _ => ErrorKind::Fatal,
+    }
+}
+
 /// A trait to convert [`AXError`] into a [`Result`] type.
 pub(crate) trait AXErrorIntoResult {
     /// Converts the [`AXError`] into a [`Result`].
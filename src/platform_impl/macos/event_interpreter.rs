@@ -1,13 +1,25 @@
 use crate::{
-    Event, EventFilter, EventTx, MaybeWindowAvailable, Window, platform_impl::PlatformWindow,
+    DegradedReason, Event, EventError, EventFilter, EventSender, MaybeWindowAvailable, Monitor,
+    ObserverEvent, Window, WindowMatcher, coalesce,
+    error::ErrorRateLimiter,
+    monitor::{self, DisplayTracker},
+    platform_impl::{PlatformWindow, is_process_running},
 };
 use accessibility::{AXUIElement, AXUIElementAttributes};
+use objc2_core_graphics::{CGEventSource, CGEventSourceStateID, CGMouseButton};
 
 #[inline]
 fn create_window_unchecked(element: AXUIElement) -> Window {
     Window::new(PlatformWindow::new(element))
 }
 
+/// Whether a window is being moved or resized.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DragKind {
+    Move,
+    Resize,
+}
+
 #[derive(Default, Clone, Debug)]
 struct EventInterpreterState {
     #[cfg(feature = "macos-private-api")]
@@ -15,27 +27,68 @@ struct EventInterpreterState {
     #[cfg(feature = "macos-private-api")]
     previous_window_ids: std::collections::HashSet<u32>,
     previous_focused_window: Option<AXUIElement>,
+    fullscreen_windows: Vec<AXUIElement>,
+    /// The last known titles of the windows for deduplicating title changes.
+    titles: Vec<(AXUIElement, String)>,
+    /// The last known displays of the windows.
+    monitors: Vec<(AXUIElement, Monitor)>,
+    /// The windows that are being moved or resized.
+    drags: Vec<(AXUIElement, DragKind)>,
+    /// The last known windows from the front to the back.
+    stacking: Vec<AXUIElement>,
+    /// Whether the application was not responding at the last health check.
+    not_responding: bool,
+}
+
+/// The only window that the interpreter dispatches events of.
+#[derive(Clone, Debug)]
+pub(crate) struct TargetWindow {
+    pub element: AXUIElement,
+    pub id: u32,
 }
 
 pub(crate) struct EventInterpreter {
     app_element: AXUIElement,
-    event_tx: EventTx,
+    /// It becomes `None` after the target window is closed so that the channel can be closed.
+    event_tx: Option<EventSender>,
     event_filter: EventFilter,
+    target: Option<TargetWindow>,
+    /// The windows that the interpreter dispatches events of.
+    matcher: Option<WindowMatcher>,
     state: EventInterpreterState,
+    /// It is `None` if no display event is observed.
+    displays: Option<DisplayTracker>,
+    errors: ErrorRateLimiter,
 }
 
+// The elements are only used on the thread of the event loop while the lock of the observer
+// is held, and releasing them is thread-safe like `WindowUIElement`.
+unsafe impl Send for EventInterpreter {}
+
 impl EventInterpreter {
     pub fn new(
         app_element: AXUIElement,
-        event_tx: EventTx,
+        event_tx: EventSender,
         event_filter: EventFilter,
+        matcher: Option<WindowMatcher>,
     ) -> Result<Self, accessibility::Error> {
         #[allow(unused_mut)]
         let mut interpreter = Self {
             app_element,
-            event_tx,
+            event_tx: Some(if event_filter.title_changed {
+                coalesce::throttle_titles(event_tx)
+            } else {
+                event_tx
+            }),
             event_filter,
+            target: None,
+            matcher,
             state: Default::default(),
+            displays: (event_filter.display_added
+                || event_filter.display_removed
+                || event_filter.display_changed)
+                .then(DisplayTracker::new),
+            errors: ErrorRateLimiter::default(),
         };
 
         #[cfg(feature = "macos-private-api")]
@@ -43,21 +96,158 @@ impl EventInterpreter {
             interpreter.refresh_window_ids_state()?;
         }
 
+        if event_filter.entered_fullscreen || event_filter.exited_fullscreen {
+            interpreter.state.fullscreen_windows = interpreter
+                .app_element
+                .windows()?
+                .iter()
+                .filter(|element| {
+                    PlatformWindow::new((*element).clone())
+                        .is_fullscreen()
+                        .is_ok_and(|fullscreen| fullscreen)
+                })
+                .map(|element| element.clone())
+                .collect();
+        }
+
+        if event_filter.title_changed {
+            interpreter.refresh_titles()?;
+        }
+
+        if event_filter.monitor_changed {
+            interpreter.refresh_monitors()?;
+        }
+
+        if event_filter.stacking_changed {
+            interpreter.state.stacking = interpreter.stacking_order()?;
+        }
+
         Ok(interpreter)
     }
 
-    fn dispatch(&self, window: Option<Window>, event: Event) {
-        if self.event_filter.should_dispatch(&event) {
-            let payload = if let Some(window) = window {
-                MaybeWindowAvailable::Available { window, event }
-            } else {
-                MaybeWindowAvailable::NotAvailable { event }
+    /// Replaces the known titles with the titles of the current windows.
+    fn refresh_titles(&mut self) -> Result<(), accessibility::Error> {
+        self.state.titles = self
+            .app_element
+            .windows()?
+            .iter()
+            .filter_map(|element| Some((element.clone(), element.title().ok()?.to_string())))
+            .collect();
+
+        Ok(())
+    }
+
+    /// Limits the events to the given window.
+    /// It also starts tracking the window IDs for detecting when the window is closed.
+    #[cfg(feature = "macos-private-api")]
+    pub fn set_target(&mut self, target: TargetWindow) -> Result<(), accessibility::Error> {
+        self.target = Some(target);
+        self.refresh_window_ids_state()
+    }
+
+    fn is_target(&self, window: Option<&Window>, event: &Event) -> bool {
+        let Some(target) = &self.target else {
+            return true;
+        };
+
+        match (window, event) {
+            (Some(window), _) => *window.inner().ax_ui_element() == target.element,
+            (None, Event::Closed { window_id }) => window_id.as_u32() == target.id,
+            _ => true,
+        }
+    }
+
+    fn dispatch(&mut self, window: Option<Window>, event: Event) {
+        self.dispatch_payload(window, event, false);
+    }
+
+    fn dispatch_initial_state(&mut self, window: Window, event: Event) {
+        self.dispatch_payload(Some(window), event, true);
+    }
+
+    fn dispatch_payload(&mut self, window: Option<Window>, event: Event, initial_state: bool) {
+        if !self.is_target(window.as_ref(), &event) {
+            return;
+        }
+
+        if let (Some(matcher), Some(window)) = (&self.matcher, &window)
+            && !matcher.matches(window.inner())
+        {
+            return;
+        }
+
+        let target_closed = self.target.is_some() && matches!(event, Event::Closed { .. });
+
+        if let Some(event_tx) = &self.event_tx
+            && self.event_filter.should_dispatch(&event)
+        {
+            let payload = match window {
+                Some(window) if initial_state => {
+                    MaybeWindowAvailable::InitialState { window, event }
+                }
+                Some(window) => MaybeWindowAvailable::Available { window, event },
+                None => MaybeWindowAvailable::NotAvailable { event },
             };
 
-            let _ = self.event_tx.send(Ok(payload));
+            let _ = event_tx.send(Ok(payload));
+        }
+
+        if target_closed {
+            // Nothing will happen to the window anymore, so the stream is ended.
+            self.event_tx = None;
+        }
+    }
+
+    /// Sends the error unless the same one was sent just before.
+    fn send_error(&mut self, error: EventError) {
+        if let Some(event_tx) = &self.event_tx
+            && let Some(error) = self.errors.check(error)
+        {
+            let _ = event_tx.send(Err(error));
         }
     }
 
+    /// Sends the events that describe the current state of the windows
+    /// followed by [`Event::InitialStateCompleted`].
+    pub fn send_initial_state(&mut self) {
+        if let Err(e) = self.dispatch_initial_state_events() {
+            self.send_error(EventError::new("sending the initial state", e));
+        }
+
+        if let Some(event_tx) = &self.event_tx {
+            let _ = event_tx.send(Ok(MaybeWindowAvailable::NotAvailable {
+                event: Event::InitialStateCompleted,
+            }));
+        }
+    }
+
+    fn dispatch_initial_state_events(&mut self) -> Result<(), accessibility::Error> {
+        let focused = self.app_element.focused_window().ok();
+
+        for element in self.app_element.windows()?.iter() {
+            let window = create_window_unchecked(element.clone());
+            let minimized = element.minimized().is_ok_and(bool::from);
+
+            self.dispatch_initial_state(window.clone(), Event::Created);
+            if minimized {
+                self.dispatch_initial_state(window.clone(), Event::Hidden);
+            } else {
+                self.dispatch_initial_state(window.clone(), Event::Showed);
+            }
+
+            if focused.as_ref() == Some(&*element) {
+                self.dispatch_initial_state(window, Event::Focused);
+            }
+        }
+
+        // The focused window has been reported, so it must be unfocused when the focus moves.
+        if self.state.previous_focused_window.is_none() {
+            self.state.previous_focused_window = focused;
+        }
+
+        Ok(())
+    }
+
     fn on_application_activated_or_deactivated(
         &mut self,
         is_deactivated: bool,
@@ -99,6 +289,56 @@ impl EventInterpreter {
         Ok(())
     }
 
+    /// Replaces the known displays with the displays of the current windows.
+    fn refresh_monitors(&mut self) -> Result<(), accessibility::Error> {
+        let monitors = Monitor::all();
+
+        self.state.monitors = self
+            .app_element
+            .windows()?
+            .iter()
+            .filter_map(|element| {
+                let bounds = PlatformWindow::new(element.clone()).bounds().ok()?;
+                let monitor = monitor::dominant(monitors.clone(), &bounds)?;
+
+                Some((element.clone(), monitor))
+            })
+            .collect();
+
+        Ok(())
+    }
+
+    fn update_monitor(&mut self, window: &Window) -> Result<(), accessibility::Error> {
+        let element = window.inner().ax_ui_element();
+        let Some(index) = self
+            .state
+            .monitors
+            .iter()
+            .position(|(known, _)| known == element)
+        else {
+            // The closed windows are forgotten when an unknown window appears.
+            return self.refresh_monitors();
+        };
+
+        let bounds = window.inner().bounds()?;
+        let Some(current) = Monitor::dominant_for(&bounds) else {
+            return Ok(());
+        };
+
+        if self.state.monitors[index].1.id != current.id {
+            let from = std::mem::replace(&mut self.state.monitors[index].1, current.clone());
+            self.dispatch(
+                Some(window.clone()),
+                Event::MonitorChanged {
+                    from: Box::new(from),
+                    to: Box::new(current),
+                },
+            );
+        }
+
+        Ok(())
+    }
+
     pub fn on_window_created(&mut self, element: AXUIElement) -> Result<(), accessibility::Error> {
         let window = create_window_unchecked(element);
         self.dispatch(Some(window), Event::Created);
@@ -119,9 +359,11 @@ impl EventInterpreter {
         let removed = self
             .state
             .previous_window_ids
-            .difference(&self.state.current_window_ids);
+            .difference(&self.state.current_window_ids)
+            .cloned()
+            .collect::<Vec<_>>();
 
-        for window_id in removed.cloned() {
+        for window_id in removed {
             let event = Event::Closed {
                 window_id: window_id.into(),
             };
@@ -131,14 +373,193 @@ impl EventInterpreter {
         Ok(())
     }
 
-    pub fn on_window_moved(&self, element: AXUIElement) {
+    fn observes_drags(&self) -> bool {
+        self.event_filter.move_started
+            || self.event_filter.move_ended
+            || self.event_filter.resize_started
+            || self.event_filter.resize_ended
+    }
+
+    /// Returns `true` if a window is being moved or resized.
+    /// The observer must call [`EventInterpreter::end_drags`] after the windows are quiet.
+    pub fn is_dragging(&self) -> bool {
+        !self.state.drags.is_empty()
+    }
+
+    /// Starts a move or a resize of the window unless it is already being moved or resized.
+    fn start_drag(&mut self, window: &Window, kind: DragKind) {
+        if !self.observes_drags()
+            || self
+                .state
+                .drags
+                .iter()
+                .any(|(element, _)| element == window.inner().ax_ui_element())
+        {
+            return;
+        }
+
+        let element = window.inner().ax_ui_element().clone();
+        self.state.drags.push((element, kind));
+
+        let event = match kind {
+            DragKind::Move => Event::MoveStarted,
+            DragKind::Resize => Event::ResizeStarted,
+        };
+        self.dispatch(Some(window.clone()), event);
+    }
+
+    /// Ends the moves and the resizes of the windows unless the left mouse button is pressed.
+    /// It returns `false` if they are not ended yet.
+    pub fn end_drags(&mut self) -> bool {
+        if CGEventSource::button_state(
+            CGEventSourceStateID::CombinedSessionState,
+            CGMouseButton::Left,
+        ) {
+            return false;
+        }
+
+        self.finish_drags();
+        true
+    }
+
+    /// Ends the moves and the resizes of the windows regardless of the mouse button.
+    pub fn finish_drags(&mut self) {
+        for (element, kind) in std::mem::take(&mut self.state.drags) {
+            let window = create_window_unchecked(element);
+
+            // The window may have been closed during the move.
+            let Ok(bounds) = window.inner().bounds() else {
+                continue;
+            };
+            let event = match kind {
+                DragKind::Move => Event::MoveEnded { bounds },
+                DragKind::Resize => Event::ResizeEnded { bounds },
+            };
+            self.dispatch(Some(window), event);
+        }
+    }
+
+    /// Dispatches [`Event::Lifecycle`].
+    pub fn dispatch_lifecycle(&mut self, event: ObserverEvent) {
+        self.dispatch(None, Event::Lifecycle { event });
+    }
+
+    /// Checks whether the application is running and responding,
+    /// and dispatches the lifecycle events of the changes.
+    /// It returns `false` if the application has exited.
+    pub fn check_health(&mut self, pid: accessibility_sys::pid_t) -> bool {
+        if !is_process_running(pid) {
+            self.dispatch_lifecycle(ObserverEvent::TargetExited { pid: pid as _ });
+            return false;
+        }
+
+        // The application didn't reply within the timeout of the Accessibility API.
+        let not_responding = matches!(
+            self.app_element.role(),
+            Err(accessibility::Error::Ax(
+                accessibility_sys::kAXErrorCannotComplete
+            ))
+        );
+
+        if not_responding != self.state.not_responding {
+            self.state.not_responding = not_responding;
+            self.dispatch_lifecycle(if not_responding {
+                ObserverEvent::Degraded {
+                    reason: DegradedReason::NotResponding,
+                }
+            } else {
+                ObserverEvent::Recovered
+            });
+        }
+
+        true
+    }
+
+    pub fn on_window_moved(&mut self, element: AXUIElement) -> Result<(), accessibility::Error> {
+        let window = create_window_unchecked(element);
+        let bounds = window.inner().bounds()?;
+        self.start_drag(&window, DragKind::Move);
+        self.dispatch(Some(window.clone()), Event::Moved { bounds });
+
+        if self.event_filter.monitor_changed {
+            self.update_monitor(&window)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn on_window_resized(&mut self, element: AXUIElement) -> Result<(), accessibility::Error> {
         let window = create_window_unchecked(element);
-        self.dispatch(Some(window), Event::Moved);
+        let bounds = window.inner().bounds()?;
+        self.start_drag(&window, DragKind::Resize);
+        self.dispatch(Some(window.clone()), Event::Resized { bounds });
+
+        if self.event_filter.monitor_changed {
+            self.update_monitor(&window)?;
+        }
+
+        // Entering and exiting the fullscreen mode resize the window.
+        self.update_fullscreen_state(window);
+
+        Ok(())
     }
 
-    pub fn on_window_resized(&self, element: AXUIElement) {
+    fn update_fullscreen_state(&mut self, window: Window) {
+        // Some applications don't support the attribute. Their windows are never fullscreen.
+        let fullscreen = window.inner().is_fullscreen().unwrap_or(false);
+        let index = self
+            .state
+            .fullscreen_windows
+            .iter()
+            .position(|element| element == window.inner().ax_ui_element());
+
+        match (fullscreen, index) {
+            (true, None) => {
+                let element = window.inner().ax_ui_element().clone();
+                self.state.fullscreen_windows.push(element);
+                self.dispatch(Some(window), Event::EnteredFullscreen);
+            }
+            (false, Some(index)) => {
+                self.state.fullscreen_windows.swap_remove(index);
+                self.dispatch(Some(window), Event::ExitedFullscreen);
+            }
+            _ => {}
+        }
+    }
+
+    pub fn on_title_changed(&mut self, element: AXUIElement) -> Result<(), accessibility::Error> {
+        // The notification is also posted for the other elements such as buttons.
+        if element.role()? != accessibility_sys::kAXWindowRole {
+            return Ok(());
+        }
+
+        let new = element.title()?.to_string();
+        let old = match self
+            .state
+            .titles
+            .iter()
+            .position(|(known, _)| *known == element)
+        {
+            Some(index) => Some(std::mem::replace(
+                &mut self.state.titles[index].1,
+                new.clone(),
+            )),
+            None => {
+                // The closed windows are forgotten when an unknown window appears.
+                self.refresh_titles()?;
+                None
+            }
+        };
+
+        // Some applications set the same title repeatedly, for example on every redraw.
+        if old.as_ref() == Some(&new) {
+            return Ok(());
+        }
+
         let window = create_window_unchecked(element);
-        self.dispatch(Some(window), Event::Resized);
+        self.dispatch(Some(window), Event::TitleChanged { old, new });
+
+        Ok(())
     }
 
     pub fn on_focused_window_changed(
@@ -174,21 +595,36 @@ impl EventInterpreter {
 
     pub fn on_window_miniaturized(&mut self, element: AXUIElement) {
         let window = create_window_unchecked(element.clone());
+        self.dispatch(Some(window.clone()), Event::Minimized);
         self.dispatch(Some(window.clone()), Event::Hidden);
         self.dispatch(Some(window), Event::Backgrounded);
     }
 
     pub fn on_window_deminimized(&mut self, element: AXUIElement) {
         let window = create_window_unchecked(element);
+        self.dispatch(Some(window.clone()), Event::Restored);
         self.dispatch(Some(window.clone()), Event::Showed);
         self.dispatch(Some(window), Event::Foregrounded);
     }
 
+    /// Dispatches the changes of the displays since the last check.
+    fn check_displays(&mut self, force: bool) {
+        let Some(displays) = &mut self.displays else {
+            return;
+        };
+
+        for event in displays.check(force) {
+            self.dispatch(None, event);
+        }
+    }
+
     fn dispatch_ax_notification(
         &mut self,
         element: AXUIElement,
         notification: &str,
     ) -> Result<bool, accessibility::Error> {
+        self.check_displays(is_geometry_notification(notification));
+
         match notification {
             accessibility_sys::kAXWindowCreatedNotification => {
                 self.on_window_created(element)?;
@@ -198,10 +634,10 @@ impl EventInterpreter {
                 self.on_ui_element_destroyed()?;
             }
             accessibility_sys::kAXWindowResizedNotification => {
-                self.on_window_resized(element);
+                self.on_window_resized(element)?;
             }
             accessibility_sys::kAXWindowMovedNotification => {
-                self.on_window_moved(element);
+                self.on_window_moved(element)?;
             }
             accessibility_sys::kAXApplicationActivatedNotification => {
                 self.on_application_activated()?;
@@ -218,17 +654,66 @@ impl EventInterpreter {
             accessibility_sys::kAXWindowDeminiaturizedNotification => {
                 self.on_window_deminimized(element);
             }
+            accessibility_sys::kAXTitleChangedNotification => {
+                self.on_title_changed(element)?;
+            }
             _ => return Ok(false),
         }
 
         Ok(true)
     }
 
+    /// Retrieves the windows of the application from the front to the back.
+    fn stacking_order(&self) -> Result<Vec<AXUIElement>, accessibility::Error> {
+        Ok(self
+            .app_element
+            .windows()?
+            .iter()
+            .map(|element| element.clone())
+            .collect())
+    }
+
+    /// Dispatches [`Event::StackingChanged`] if the order of the windows was changed.
+    fn update_stacking(&mut self) -> Result<(), accessibility::Error> {
+        let stacking = self.stacking_order()?;
+
+        if stacking != self.state.stacking {
+            self.state.stacking = stacking.clone();
+
+            let windows = stacking.into_iter().map(create_window_unchecked).collect();
+            self.dispatch(None, Event::StackingChanged { windows });
+        }
+
+        Ok(())
+    }
+
     pub fn interpret_ax_notification(&mut self, element: AXUIElement, notification: &str) -> bool {
-        match self.dispatch_ax_notification(element, notification) {
+        #[cfg(feature = "macos-private-api")]
+        let window_id = super::binding_ax_function::ax_ui_element_get_window_id(&element)
+            .ok()
+            .map(window_getter::WindowId::new);
+        #[cfg(not(feature = "macos-private-api"))]
+        let window_id = None;
+
+        let result = self
+            .dispatch_ax_notification(element, notification)
+            .and_then(|dispatched| {
+                // The geometry notifications don't change the order and they are too frequent.
+                if self.event_filter.stacking_changed && !is_geometry_notification(notification) {
+                    self.update_stacking()?;
+                }
+
+                Ok(dispatched)
+            });
+
+        match result {
             Ok(dispatched) => dispatched,
             Err(e) => {
-                let _ = self.event_tx.send(Err(e));
+                self.send_error(
+                    EventError::new("interpreting a notification", e)
+                        .with_window_id(window_id)
+                        .with_raw_event(notification),
+                );
                 false
             }
         }
@@ -269,6 +754,18 @@ pub(crate) fn for_each_notification_event<E>(
         f(accessibility_sys::kAXWindowDeminiaturizedNotification)?;
     }
 
+    if event_filter.minimized {
+        f(accessibility_sys::kAXWindowMiniaturizedNotification)?;
+    }
+
+    if event_filter.restored {
+        f(accessibility_sys::kAXWindowDeminiaturizedNotification)?;
+    }
+
+    if event_filter.entered_fullscreen || event_filter.exited_fullscreen {
+        f(accessibility_sys::kAXWindowResizedNotification)?;
+    }
+
     if event_filter.moved {
         f(accessibility_sys::kAXWindowMovedNotification)?;
     }
@@ -277,6 +774,16 @@ pub(crate) fn for_each_notification_event<E>(
         f(accessibility_sys::kAXWindowResizedNotification)?;
     }
 
+    // The moves and the resizes are detected from the changes of the geometry.
+    if event_filter.move_started
+        || event_filter.move_ended
+        || event_filter.resize_started
+        || event_filter.resize_ended
+    {
+        f(accessibility_sys::kAXWindowMovedNotification)?;
+        f(accessibility_sys::kAXWindowResizedNotification)?;
+    }
+
     if event_filter.created {
         f(accessibility_sys::kAXWindowCreatedNotification)?;
     }
@@ -285,5 +792,51 @@ pub(crate) fn for_each_notification_event<E>(
         f(accessibility_sys::kAXUIElementDestroyedNotification)?;
     }
 
+    if event_filter.title_changed {
+        f(accessibility_sys::kAXTitleChangedNotification)?;
+    }
+
+    // The order of the windows is checked after the notifications that can change it.
+    if event_filter.stacking_changed {
+        f(accessibility_sys::kAXFocusedWindowChangedNotification)?;
+        f(accessibility_sys::kAXWindowCreatedNotification)?;
+        f(accessibility_sys::kAXUIElementDestroyedNotification)?;
+        f(accessibility_sys::kAXWindowMiniaturizedNotification)?;
+        f(accessibility_sys::kAXWindowDeminiaturizedNotification)?;
+    }
+
+    if event_filter.monitor_changed {
+        f(accessibility_sys::kAXWindowMovedNotification)?;
+        f(accessibility_sys::kAXWindowResizedNotification)?;
+    }
+
+    if event_filter.display_added || event_filter.display_removed || event_filter.display_changed {
+        f(accessibility_sys::kAXWindowMovedNotification)?;
+        f(accessibility_sys::kAXWindowResizedNotification)?;
+    }
+
     Ok(())
 }
+
+/// Checks whether the notification is about moving or resizing a window.
+pub(crate) fn is_geometry_notification(notification: &str) -> bool {
+    matches!(
+        notification,
+        accessibility_sys::kAXWindowMovedNotification
+            | accessibility_sys::kAXWindowResizedNotification
+    )
+}
+
+/// Checks whether the notification can be observed on a window element
+/// instead of the application element.
+pub(crate) fn is_window_notification(notification: &str) -> bool {
+    matches!(
+        notification,
+        accessibility_sys::kAXWindowMovedNotification
+            | accessibility_sys::kAXWindowResizedNotification
+            | accessibility_sys::kAXWindowMiniaturizedNotification
+            | accessibility_sys::kAXWindowDeminiaturizedNotification
+            | accessibility_sys::kAXUIElementDestroyedNotification
+            | accessibility_sys::kAXTitleChangedNotification
+    )
+}
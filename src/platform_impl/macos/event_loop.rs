@@ -1,11 +1,16 @@
-use std::thread::{self, JoinHandle};
+use std::{ffi::c_void, sync::OnceLock, thread::JoinHandle, time::Duration};
 
-use objc2_core_foundation::{CFRetained, CFRunLoop, CFRunLoopSource, kCFRunLoopDefaultMode};
-use tokio::sync::OnceCell;
+use objc2_core_foundation::{
+    CFAbsoluteTimeGetCurrent, CFRetained, CFRunLoop, CFRunLoopSource, CFRunLoopTimer,
+    CFRunLoopTimerContext, kCFRunLoopDefaultMode,
+};
 
 use super::binding_ax_observer::AXObserver;
 
 /// The wrapper of [`CFRunLoop`] for [`AXObserver`].
+///
+/// The run loop keeps running when all the observers have been removed,
+/// so that the next observers can be registered on it.
 pub struct EventLoop {
     run_loop: CFRetained<CFRunLoop>,
     handle: JoinHandle<()>,
@@ -14,8 +19,8 @@ unsafe impl Send for EventLoop {}
 unsafe impl Sync for EventLoop {}
 
 impl EventLoop {
-    pub async fn new() -> Self {
-        let (tx, rx) = tokio::sync::oneshot::channel();
+    pub fn new() -> Self {
+        let (tx, rx) = std::sync::mpsc::sync_channel(1);
 
         #[derive(Debug)]
         struct SendRunLoop(CFRetained<CFRunLoop>);
@@ -24,13 +29,17 @@ impl EventLoop {
 
         let handle = std::thread::spawn(move || {
             let run_loop = CFRunLoop::current().unwrap();
-            tx.send(SendRunLoop(CFRetained::clone(&run_loop))).unwrap();
 
-            thread::park();
+            // `CFRunLoopRun` returns when the run loop has no sources or timers,
+            // so a timer that never fires keeps it running.
+            let keep_alive = Timer::new(Box::new(|| None));
+            run_loop.add_timer(Some(&keep_alive.timer), unsafe { kCFRunLoopDefaultMode });
+
+            tx.send(SendRunLoop(CFRetained::clone(&run_loop))).unwrap();
 
             CFRunLoop::run();
         });
-        let run_loop = rx.await.unwrap().0;
+        let run_loop = rx.recv().unwrap().0;
 
         Self { run_loop, handle }
     }
@@ -40,8 +49,6 @@ impl EventLoop {
         let mode = unsafe { kCFRunLoopDefaultMode };
 
         self.run_loop.add_source(source, mode);
-
-        self.handle.thread().unpark();
     }
 
     pub fn unregister(&self, source: CFRetained<CFRunLoopSource>) {
@@ -50,16 +57,25 @@ impl EventLoop {
 
         self.run_loop.remove_source(source, mode);
     }
+
+    /// Returns `false` if the thread of the event loop has ended.
+    pub fn is_running(&self) -> bool {
+        !self.handle.is_finished()
+    }
+
+    pub fn add_timer(&self, timer: &Timer) {
+        let mode = unsafe { kCFRunLoopDefaultMode };
+
+        self.run_loop.add_timer(Some(&timer.timer), mode);
+    }
 }
 
-static EVENT_LOOP: OnceCell<EventLoop> = OnceCell::const_new();
+static EVENT_LOOP: OnceLock<EventLoop> = OnceLock::new();
 
 /// Returns a reference to the global event loop for the observer.
 /// This function initializes the event loop and starts it if it hasn't been initialized yet.
-pub async fn event_loop() -> &'static EventLoop {
-    EVENT_LOOP
-        .get_or_init(|| async { EventLoop::new().await })
-        .await
+pub fn event_loop() -> &'static EventLoop {
+    EVENT_LOOP.get_or_init(EventLoop::new)
 }
 
 pub fn get_event_loop<'a>() -> Option<&'a EventLoop> {
@@ -90,3 +106,106 @@ impl ObserverSource {
         CFRetained::clone(&self.source)
     }
 }
+
+/// The interval of [`Timer`] that keeps it from firing until it is scheduled again.
+const IDLE_INTERVAL: f64 = 60. * 60. * 24. * 365.;
+
+/// The callback of [`Timer`]. It returns the delay to fire again after, if needed.
+type TimerCallback = Box<dyn FnMut() -> Option<Duration>>;
+
+/// A timer on the event loop which calls the callback when it fires.
+/// It does nothing until it is scheduled and it is stopped by [`Timer::invalidate`].
+///
+/// The callback is only called on the thread of the event loop.
+#[derive(Clone)]
+pub struct Timer {
+    timer: CFRetained<CFRunLoopTimer>,
+}
+unsafe impl Send for Timer {}
+unsafe impl Sync for Timer {}
+
+impl Timer {
+    pub fn new(callback: TimerCallback) -> Self {
+        unsafe extern "C-unwind" fn fire(timer: *mut CFRunLoopTimer, info: *mut c_void) {
+            let callback = unsafe { &mut *(info as *mut TimerCallback) };
+
+            if let Some(delay) = callback() {
+                let timer = unsafe { &*timer };
+                timer.set_next_fire_date(CFAbsoluteTimeGetCurrent() + delay.as_secs_f64());
+            }
+        }
+
+        unsafe extern "C-unwind" fn release(info: *const c_void) {
+            drop(unsafe { Box::from_raw(info as *mut TimerCallback) });
+        }
+
+        let mut context = CFRunLoopTimerContext {
+            version: 0,
+            info: Box::into_raw(Box::new(callback)) as *mut c_void,
+            retain: None,
+            release: Some(release),
+            copyDescription: None,
+        };
+
+        // The timer repeats so that it stays valid after firing.
+        let timer = unsafe {
+            CFRunLoopTimer::new(
+                None,
+                CFAbsoluteTimeGetCurrent() + IDLE_INTERVAL,
+                IDLE_INTERVAL,
+                0,
+                0,
+                Some(fire),
+                &mut context,
+            )
+        }
+        .expect("Failed to create a timer.");
+
+        Self { timer }
+    }
+
+    /// Makes the timer fire after the given delay instead of the previously scheduled time.
+    pub fn schedule(&self, delay: Duration) {
+        self.timer
+            .set_next_fire_date(CFAbsoluteTimeGetCurrent() + delay.as_secs_f64());
+    }
+
+    /// Removes the timer from the event loop. The callback is released with the timer.
+    pub fn invalidate(&self) {
+        self.timer.invalidate();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+
+    use super::*;
+
+    /// Adds a timer that fires once and waits until it has fired.
+    fn fire_timer(event_loop: &EventLoop) -> bool {
+        let (tx, rx) = mpsc::channel();
+        let timer = Timer::new(Box::new(move || {
+            let _ = tx.send(());
+            None
+        }));
+        event_loop.add_timer(&timer);
+        timer.schedule(Duration::ZERO);
+
+        let fired = rx.recv_timeout(Duration::from_secs(5)).is_ok();
+        timer.invalidate();
+        fired
+    }
+
+    #[test]
+    fn keeps_running_after_the_last_timer_is_invalidated() {
+        let event_loop = EventLoop::new();
+
+        assert!(fire_timer(&event_loop));
+        // The run loop would return here without the timer that keeps it alive.
+        std::thread::sleep(Duration::from_millis(100));
+        assert!(event_loop.is_running());
+
+        assert!(fire_timer(&event_loop));
+    }
+}
@@ -2,8 +2,10 @@
 
 pub mod binding_ax_function;
 pub mod binding_ax_observer;
+pub(crate) mod display;
 pub mod error;
 mod event_interpreter;
 mod event_loop;
+pub mod process;
 pub mod window;
 pub mod window_observer;
@@ -0,0 +1,25 @@
+use std::{ffi::OsString, os::unix::ffi::OsStringExt, path::PathBuf};
+
+use accessibility_sys::pid_t;
+
+/// Checks whether the process with the given ID is still running.
+pub fn is_process_running(pid: pid_t) -> bool {
+    // Signal `0` performs only the existence and permission checks.
+    // `EPERM` means that the process exists but belongs to another user.
+    let result = unsafe { libc::kill(pid, 0) };
+
+    result == 0 || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+/// Retrieves the path of the executable of the process with the given ID.
+pub fn process_path(pid: pid_t) -> Option<PathBuf> {
+    let mut buffer = vec![0u8; libc::PROC_PIDPATHINFO_MAXSIZE as usize];
+    let length = unsafe { libc::proc_pidpath(pid, buffer.as_mut_ptr() as _, buffer.len() as u32) };
+
+    if length <= 0 {
+        return None;
+    }
+
+    buffer.truncate(length as usize);
+    Some(OsString::from_vec(buffer).into())
+}
@@ -1,11 +1,19 @@
-use accessibility::{AXUIElement, AXUIElementAttributes};
+use accessibility::{AXAttribute, AXUIElement, AXUIElementAttributes};
+use accessibility_sys::AXUIElementRef;
+use core_foundation::{base::TCFType, boolean::CFBoolean, string::CFString};
 use objc2_core_foundation::{CGPoint, CGSize};
 
 use super::{
-    binding_ax_function::{ax_ui_element_copy_attribute_value, ax_value_get_value},
+    binding_ax_function::{
+        ax_ui_element_copy_attribute_value, ax_ui_element_copy_multiple_attribute_values,
+        ax_value_create, ax_value_get_typed_value, ax_value_get_value,
+    },
     error::MacOSError,
 };
-use crate::window::{Position, Size};
+use crate::{
+    matcher::{WindowProperties, WindowRole},
+    window::{Position, Rect, Size},
+};
 
 impl From<CGSize> for Size {
     fn from(size: CGSize) -> Self {
@@ -25,6 +33,17 @@ impl From<CGPoint> for Position {
     }
 }
 
+/// The attribute of the native fullscreen mode. It is not defined by `accessibility-sys`.
+const AX_FULL_SCREEN_ATTRIBUTE: &str = "AXFullScreen";
+
+/// Retrieves a boolean attribute that has no accessor in `accessibility`.
+fn get_bool(element: &AXUIElement, attribute: &'static str) -> Result<bool, MacOSError> {
+    let attribute = AXAttribute::new(&CFString::from_static_string(attribute));
+    let value = element.attribute(&attribute)?;
+
+    Ok(value.downcast::<CFBoolean>().is_some_and(bool::from))
+}
+
 /// Represents a macOS window and provides methods to interact with it.
 /// This is the wrapper of [`AXUIElement`] which represents a window.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -61,6 +80,30 @@ impl WindowUIElement {
         Ok(unsafe { ax_value_get_value::<T>(ax_value as _, r#type).unwrap() })
     }
 
+    /// Sets a specific attribute of the window via [`AXUIElement`].
+    fn set<T>(
+        &self,
+        attribute: &'static str,
+        r#type: accessibility_sys::AXValueType,
+        value: T,
+    ) -> Result<(), MacOSError> {
+        let value = unsafe { ax_value_create(r#type, &value) }
+            .ok_or(MacOSError::Ax(accessibility_sys::kAXErrorIllegalArgument))?;
+
+        self.0.set_attribute(
+            &AXAttribute::new(&CFString::from_static_string(attribute)),
+            value,
+        )
+    }
+
+    /// Sets a boolean attribute of the window via [`AXUIElement`].
+    fn set_bool(&self, attribute: &'static str, value: bool) -> Result<(), MacOSError> {
+        self.0.set_attribute(
+            &AXAttribute::new(&CFString::from_static_string(attribute)),
+            CFBoolean::from(value).as_CFType(),
+        )
+    }
+
     /// Retrieves the title of the window.
     pub fn title(&self) -> Result<String, MacOSError> {
         Ok(self.0.title()?.to_string())
@@ -84,11 +127,142 @@ impl WindowUIElement {
         .map(|v| v.into())
     }
 
+    /// Retrieves the position and the size of the window in a single query,
+    /// so they are consistent even while the window is moving.
+    pub fn bounds(&self) -> Result<Rect, MacOSError> {
+        let values = ax_ui_element_copy_multiple_attribute_values(
+            &self.0,
+            &[
+                accessibility_sys::kAXPositionAttribute,
+                accessibility_sys::kAXSizeAttribute,
+            ],
+        )
+        .map_err(MacOSError::Ax)?;
+        let (Some(position), Some(size)) = (values.get(0), values.get(1)) else {
+            return Err(MacOSError::Ax(accessibility_sys::kAXErrorFailure));
+        };
+
+        let position: CGPoint =
+            ax_value_get_typed_value(&position, accessibility_sys::kAXValueTypeCGPoint)
+                .map_err(MacOSError::Ax)?;
+        let size: CGSize = ax_value_get_typed_value(&size, accessibility_sys::kAXValueTypeCGSize)
+            .map_err(MacOSError::Ax)?;
+
+        Ok(Rect::new(position.into(), size.into()))
+    }
+
+    /// Moves the window to the given position.
+    pub fn set_position(&self, position: Position) -> Result<(), MacOSError> {
+        self.set(
+            accessibility_sys::kAXPositionAttribute,
+            accessibility_sys::kAXValueTypeCGPoint,
+            CGPoint::new(position.x as _, position.y as _),
+        )
+    }
+
+    /// Resizes the window to the given size.
+    pub fn set_size(&self, size: Size) -> Result<(), MacOSError> {
+        self.set(
+            accessibility_sys::kAXSizeAttribute,
+            accessibility_sys::kAXValueTypeCGSize,
+            CGSize::new(size.width as _, size.height as _),
+        )
+    }
+
+    /// Brings the window to the front of the other windows of its application.
+    pub fn raise(&self) -> Result<(), MacOSError> {
+        self.0.perform_action(&CFString::from_static_string(
+            accessibility_sys::kAXRaiseAction,
+        ))
+    }
+
+    /// Activates the application of the window and makes the window its main window.
+    pub fn focus(&self) -> Result<(), MacOSError> {
+        self.0.parent()?.set_frontmost(true)?;
+        self.0.set_main(true)?;
+        self.raise()
+    }
+
+    /// Miniaturizes the window into the Dock or restores it.
+    pub fn set_minimized(&self, minimized: bool) -> Result<(), MacOSError> {
+        self.set_bool(accessibility_sys::kAXMinimizedAttribute, minimized)
+    }
+
+    /// Enters or exits the native fullscreen mode.
+    pub fn set_fullscreen(&self, fullscreen: bool) -> Result<(), MacOSError> {
+        self.set_bool(AX_FULL_SCREEN_ATTRIBUTE, fullscreen)
+    }
+
+    /// Presses the close button of the window.
+    /// It returns `false` if the window has no close button.
+    pub fn close(&self) -> Result<bool, MacOSError> {
+        let button = match ax_ui_element_copy_attribute_value(
+            &self.0,
+            accessibility_sys::kAXCloseButtonAttribute,
+        ) {
+            Ok(button) if !button.is_null() => unsafe {
+                AXUIElement::wrap_under_create_rule(button as AXUIElementRef)
+            },
+            Ok(_)
+            | Err(accessibility_sys::kAXErrorNoValue)
+            | Err(accessibility_sys::kAXErrorAttributeUnsupported) => return Ok(false),
+            Err(e) => return Err(MacOSError::Ax(e)),
+        };
+
+        button.perform_action(&CFString::from_static_string(
+            accessibility_sys::kAXPressAction,
+        ))?;
+
+        Ok(true)
+    }
+
     /// Checks if the window is currently active.
     pub fn is_focused(&self) -> Result<bool, MacOSError> {
         Ok(self.0.focused()?.into())
     }
 
+    /// Checks if the window is miniaturized into the Dock.
+    pub fn is_minimized(&self) -> Result<bool, MacOSError> {
+        Ok(self.0.minimized()?.into())
+    }
+
+    /// Checks if the window is in the native fullscreen mode.
+    pub fn is_fullscreen(&self) -> Result<bool, MacOSError> {
+        get_bool(&self.0, AX_FULL_SCREEN_ATTRIBUTE)
+    }
+
+    /// Checks if the window is neither miniaturized nor hidden with its application.
+    pub fn is_visible(&self) -> Result<bool, MacOSError> {
+        if self.is_minimized()? {
+            return Ok(false);
+        }
+
+        let application = self.0.parent()?;
+
+        Ok(!get_bool(
+            &application,
+            accessibility_sys::kAXHiddenAttribute,
+        )?)
+    }
+
+    /// Retrieves the position of the window in the windows of its application
+    /// from the front to the back.
+    pub fn z_index(&self) -> Result<Option<usize>, MacOSError> {
+        let application = self.0.parent()?;
+
+        Ok(application
+            .windows()?
+            .iter()
+            .position(|element| *element == self.0))
+    }
+
+    /// Retrieves the ID of the process that owns the window.
+    pub fn pid(&self) -> Result<u32, MacOSError> {
+        super::binding_ax_function::ax_ui_element_get_pid(&self.0)
+            .map(|pid| pid as _)
+            .map_err(MacOSError::Ax)
+    }
+
     /// Retrieves the id of the window. The value is [`CGWindowID`][window_id].
     ///
     /// # Warning
@@ -103,3 +277,39 @@ impl WindowUIElement {
         super::binding_ax_function::ax_ui_element_get_window_id(&self.0)
     }
 }
+
+impl WindowProperties for WindowUIElement {
+    fn title(&self) -> Option<String> {
+        WindowUIElement::title(self).ok()
+    }
+
+    fn app_id(&self) -> Option<String> {
+        Some(self.0.parent().ok()?.title().ok()?.to_string())
+    }
+
+    fn role(&self) -> Option<WindowRole> {
+        let subrole = match self.0.subrole() {
+            Ok(subrole) => subrole.to_string(),
+            // The window has no subrole, such as a menu of some applications.
+            Err(MacOSError::Ax(
+                accessibility_sys::kAXErrorNoValue
+                | accessibility_sys::kAXErrorAttributeUnsupported,
+            )) => return Some(WindowRole::Other),
+            Err(_) => return None,
+        };
+
+        Some(match subrole.as_str() {
+            accessibility_sys::kAXStandardWindowSubrole => WindowRole::Standard,
+            accessibility_sys::kAXDialogSubrole | accessibility_sys::kAXSystemDialogSubrole => {
+                WindowRole::Dialog
+            }
+            accessibility_sys::kAXFloatingWindowSubrole
+            | accessibility_sys::kAXSystemFloatingWindowSubrole => WindowRole::Panel,
+            _ => WindowRole::Other,
+        })
+    }
+
+    fn bounds(&self) -> Option<Rect> {
+        WindowUIElement::bounds(self).ok()
+    }
+}
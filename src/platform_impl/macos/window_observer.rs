@@ -1,19 +1,56 @@
+use std::{
+    sync::{Arc, Mutex, MutexGuard},
+    thread,
+    time::Duration,
+};
+
 use accessibility::AXUIElement;
 
 use super::{
     binding_ax_function::ax_is_process_trusted,
     binding_ax_observer::AXObserver,
-    event_loop::{ObserverSource, event_loop, get_event_loop},
+    event_loop::{ObserverSource, Timer, event_loop, get_event_loop},
 };
 use crate::{
-    Error, EventFilter, EventTx,
-    platform_impl::macos::event_interpreter::{EventInterpreter, for_each_notification_event},
+    Capabilities, Error, EventFilter, EventSender, ObserverEvent, ObserverOptions,
+    executor::oneshot,
+    platform_impl::macos::event_interpreter::{
+        EventInterpreter, TargetWindow, for_each_notification_event, is_geometry_notification,
+        is_window_notification,
+    },
 };
 
+/// The period without moves and resizes after which they are considered ended.
+const DRAG_QUIET_PERIOD: Duration = Duration::from_millis(300);
+/// How often the application is checked whether it is running and responding.
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+/// How long [`MacOSWindowObserver::stop`] waits for the event loop to stop the observer.
+const STOP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The interpreter shared by the callbacks of the observer and the timers.
+///
+/// The callbacks run on the thread of the event loop, but they are released with
+/// [`Timer`] and [`ObserverSource`], which may happen on the thread that drops the observer.
+/// So the interpreter is shared by [`Arc`] and [`Mutex`] instead of `Rc` and `RefCell`.
+type SharedInterpreter = Arc<Mutex<EventInterpreter>>;
+
+fn lock(event_interpreter: &SharedInterpreter) -> MutexGuard<'_, EventInterpreter> {
+    event_interpreter.lock().unwrap_or_else(|e| e.into_inner())
+}
+
 /// Observes macOS window events and provides an interface to manage them.
 /// This is wrapper of [`AXObserver`].
 pub struct MacOSWindowObserver {
     source: ObserverSource,
+    /// The timer for ending moves and resizes. It is `None` if they are not observed.
+    drag_timer: Option<Timer>,
+    /// The timer for checking the application. It is `None` if the lifecycle is not observed.
+    health_timer: Option<Timer>,
+    /// The timer that stops the observer on the thread of the event loop.
+    stop_timer: Timer,
+    /// Sends whether the observer was stopped on the thread of the event loop.
+    stopped_tx: oneshot::Sender<bool>,
+    stopped_rx: oneshot::Receiver<bool>,
     stopped: bool,
 }
 
@@ -21,18 +58,138 @@ impl MacOSWindowObserver {
     /// Creates a new `MacOSWindowObserver` for a given process ID and event channel.
     pub async fn start(
         pid: accessibility_sys::pid_t,
-        event_tx: EventTx,
+        event_tx: EventSender,
+        event_filter: EventFilter,
+    ) -> Result<Self, Error> {
+        Self::start_with_options(pid, event_tx, event_filter.into()).await
+    }
+
+    pub(crate) async fn start_with_options(
+        pid: accessibility_sys::pid_t,
+        event_tx: EventSender,
+        options: ObserverOptions,
+    ) -> Result<Self, Error> {
+        Self::start_with_target(pid, None, event_tx, options).await
+    }
+
+    /// Creates a new `MacOSWindowObserver` that observes only the window
+    /// which has the given [`CGWindowID`][CGWindowID].
+    ///
+    /// The notifications about the window itself are registered to the window element,
+    /// so the other windows of the application don't wake up the observer for them.
+    ///
+    /// [CGWindowID]: https://developer.apple.com/documentation/coregraphics/cgwindowid?language=objc
+    #[cfg(feature = "macos-private-api")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "macos-private-api")))]
+    pub async fn start_for_window(
+        window_id: u32,
+        event_tx: EventSender,
         event_filter: EventFilter,
     ) -> Result<Self, Error> {
+        Self::start_for_window_with_options(window_id, event_tx, event_filter.into()).await
+    }
+
+    #[cfg(feature = "macos-private-api")]
+    pub(crate) async fn start_for_window_with_options(
+        window_id: u32,
+        event_tx: EventSender,
+        options: ObserverOptions,
+    ) -> Result<Self, Error> {
+        use accessibility::AXUIElementAttributes;
+
+        let not_found = || Error::WindowNotFound(window_id.into());
+
+        let window = window_getter::get_window(window_id.into())
+            .ok()
+            .flatten()
+            .ok_or_else(not_found)?;
+        let pid = window.owner_pid().map_err(|_| not_found())?;
+
+        if !ax_is_process_trusted() {
+            return Err(Error::PermissionDenied);
+        };
+
+        let element = AXUIElement::application(pid)
+            .windows()?
+            .iter()
+            .find(|element| {
+                super::binding_ax_function::ax_ui_element_get_window_id(element)
+                    .is_ok_and(|id| id == window_id)
+            })
+            .map(|element| element.clone())
+            .ok_or_else(not_found)?;
+
+        let target = TargetWindow {
+            element,
+            id: window_id,
+        };
+
+        Self::start_with_target(pid, Some(target), event_tx, options).await
+    }
+
+    async fn start_with_target(
+        pid: accessibility_sys::pid_t,
+        target: Option<TargetWindow>,
+        event_tx: EventSender,
+        options: ObserverOptions,
+    ) -> Result<Self, Error> {
+        let event_filter = options.event_filter;
+
         if !ax_is_process_trusted() {
             return Err(Error::PermissionDenied);
         };
 
         // Instantiate `AXObserver`.
-        let mut event_interpreter =
-            EventInterpreter::new(AXUIElement::application(pid), event_tx, event_filter)?;
-        let callback = move |element: AXUIElement, notification: String| {
-            event_interpreter.interpret_ax_notification(element, &notification);
+        #[allow(unused_mut)]
+        let mut event_interpreter = EventInterpreter::new(
+            AXUIElement::application(pid),
+            event_tx,
+            event_filter,
+            options.matcher,
+        )?;
+
+        #[cfg(feature = "macos-private-api")]
+        if let Some(target) = target.clone() {
+            event_interpreter.set_target(target)?;
+        }
+
+        // The notification about closing is always needed for ending the events of the target window.
+        let registered_filter = EventFilter {
+            closed: event_filter.closed || target.is_some(),
+            ..event_filter
+        };
+
+        let event_interpreter: SharedInterpreter = Arc::new(Mutex::new(event_interpreter));
+
+        let drag_timer = (event_filter.move_started
+            || event_filter.move_ended
+            || event_filter.resize_started
+            || event_filter.resize_ended)
+            .then(|| {
+                let event_interpreter = Arc::clone(&event_interpreter);
+
+                Timer::new(Box::new(move || {
+                    // The left mouse button may be held without moving the window.
+                    (!lock(&event_interpreter).end_drags()).then_some(DRAG_QUIET_PERIOD)
+                }))
+            });
+
+        let callback = {
+            let event_interpreter = Arc::clone(&event_interpreter);
+            let drag_timer = drag_timer.clone();
+
+            move |element: AXUIElement, notification: String| {
+                let mut event_interpreter = lock(&event_interpreter);
+                event_interpreter.interpret_ax_notification(element, &notification);
+
+                // The moves and the resizes end when the windows are quiet for a while.
+                if let Some(drag_timer) = &drag_timer
+                    && event_interpreter.is_dragging()
+                    && is_geometry_notification(&notification)
+                {
+                    drag_timer.schedule(DRAG_QUIET_PERIOD);
+                }
+            }
         };
 
         let observer =
@@ -41,8 +198,17 @@ impl MacOSWindowObserver {
         // Add the event filter to the observer.
         let app_element = AXUIElement::application(pid);
 
-        for_each_notification_event(event_filter, |notification| {
-            if let Err(ax_error) = observer.add_notification(&app_element, notification) {
+        for_each_notification_event(registered_filter, |notification| {
+            let element = match &target {
+                // The target window already exists, so it can't be created.
+                Some(_) if notification == accessibility_sys::kAXWindowCreatedNotification => {
+                    return Ok(());
+                }
+                Some(target) if is_window_notification(notification) => &target.element,
+                _ => &app_element,
+            };
+
+            if let Err(ax_error) = observer.add_notification(element, notification) {
                 return Err::<_, Error>(match ax_error {
                     accessibility_sys::kAXErrorCannotComplete => Error::InvalidProcessId(pid as _),
                     accessibility_sys::kAXErrorNotificationUnsupported => Error::NotSupported,
@@ -57,29 +223,144 @@ impl MacOSWindowObserver {
         // Wrap the observer in struct for preventing it from being dropped.
         let source = ObserverSource::new(observer);
 
+        let health_timer = event_filter.lifecycle.then(|| {
+            let event_interpreter = Arc::clone(&event_interpreter);
+
+            Timer::new(Box::new(move || {
+                // Nothing is checked anymore after the application has exited.
+                lock(&event_interpreter)
+                    .check_health(pid)
+                    .then_some(HEALTH_CHECK_INTERVAL)
+            }))
+        });
+
+        let (stopped_tx, stopped_rx) = oneshot::channel();
+        let stop_timer = {
+            let event_interpreter = Arc::clone(&event_interpreter);
+            let source = source.get();
+            let timers = [drag_timer.clone(), health_timer.clone()];
+            let stopped_tx = stopped_tx.clone();
+
+            // The callbacks of the observer and the timers run on the same thread,
+            // so nothing is dispatched after the marker.
+            Timer::new(Box::new(move || {
+                event_loop().unregister(source.clone());
+                timers.iter().flatten().for_each(Timer::invalidate);
+
+                let mut event_interpreter = lock(&event_interpreter);
+                event_interpreter.finish_drags();
+                event_interpreter.dispatch_lifecycle(ObserverEvent::Stopped);
+
+                stopped_tx.send(true);
+
+                None
+            }))
+        };
+
+        // The snapshot is sent before the observer is registered to the event loop,
+        // so it always precedes the events of changes.
+        {
+            let mut event_interpreter = lock(&event_interpreter);
+            event_interpreter.dispatch_lifecycle(ObserverEvent::Started);
+
+            if options.initial_snapshot {
+                event_interpreter.send_initial_state();
+            }
+        }
+
+        for timer in [&drag_timer, &health_timer].into_iter().flatten() {
+            event_loop().add_timer(timer);
+        }
+        if let Some(health_timer) = &health_timer {
+            health_timer.schedule(HEALTH_CHECK_INTERVAL);
+        }
+        event_loop().add_timer(&stop_timer);
+
         // Register the observer to the event loop. It will start receiving events.
-        event_loop().await.register(source.get());
+        event_loop().register(source.get());
 
         Ok(Self {
             source,
+            drag_timer,
+            health_timer,
+            stop_timer,
+            stopped_tx,
+            stopped_rx,
             stopped: false,
         })
     }
 
+    /// Returns the features supported by the macOS backend.
+    pub fn capabilities() -> Capabilities {
+        Capabilities {
+            events: EventFilter {
+                // Closed windows are detected by their IDs, which requires the private API.
+                closed: cfg!(feature = "macos-private-api"),
+                process_attached: false,
+                process_detached: false,
+                // macOS has no maximized state.
+                maximized: false,
+                unmaximized: false,
+                ..EventFilter::all()
+            },
+            window_target: cfg!(feature = "macos-private-api"),
+        }
+    }
+
+    /// Returns `false` if the event loop has ended and no events are received anymore.
+    pub fn is_running(&self) -> bool {
+        get_event_loop().is_some_and(|event_loop| event_loop.is_running())
+    }
+
     /// Stops the observer.
+    ///
+    /// The pending moves and resizes are ended and [`ObserverEvent::Stopped`] is dispatched
+    /// on the thread of the event loop. It gives up waiting for them if the event loop is
+    /// blocked for a long time, for example by an application that doesn't respond.
     pub async fn stop(mut self) {
-        event_loop().await.unregister(self.source.get());
+        self.stop_timer.schedule(Duration::ZERO);
+
+        // The caller's executor is not blocked, so the timeout is measured by another thread,
+        // which ends as soon as the observer is stopped.
+        let timeout_tx = self.stopped_tx.clone();
+        thread::spawn(move || {
+            if !timeout_tx.wait_timeout(STOP_TIMEOUT) {
+                timeout_tx.send(false);
+            }
+        });
+
+        if !(&mut self.stopped_rx).await.unwrap_or(false) {
+            self.remove_from_event_loop();
+        }
+        self.stop_timer.invalidate();
+
         self.stopped = true;
     }
+
+    fn remove_from_event_loop(&self) {
+        get_event_loop()
+            .expect("The event loop is not started.")
+            .unregister(self.source.get());
+
+        for timer in [
+            &self.drag_timer,
+            &self.health_timer,
+            &Some(self.stop_timer.clone()),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            timer.invalidate();
+        }
+    }
 }
 
 impl Drop for MacOSWindowObserver {
     fn drop(&mut self) {
         if !self.stopped {
             // Unregister the observer in case the `stop` method was not called.
-            get_event_loop()
-                .expect("The event loop is not started.")
-                .unregister(self.source.get());
+            self.remove_from_event_loop();
+
             self.stopped = true;
         }
     }
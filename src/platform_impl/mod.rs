@@ -1,8 +1,12 @@
 #[cfg(target_os = "macos")]
 pub mod macos;
 #[cfg(target_os = "macos")]
+pub(crate) use macos::error::error_kind;
+#[cfg(target_os = "macos")]
 pub use macos::{
-    error::MacOSError as PlatformError, window::WindowUIElement as PlatformWindow,
+    error::MacOSError as PlatformError,
+    process::{is_process_running, process_path},
+    window::WindowUIElement as PlatformWindow,
     window_observer::MacOSWindowObserver as PlatformWindowObserver,
 };
 
@@ -11,6 +15,10 @@ pub mod windows;
 #[cfg(target_os = "windows")]
 pub use window_getter::platform_impl::windows::WindowsWindow as PlatformWindow;
 #[cfg(target_os = "windows")]
+pub(crate) use windows::error::error_kind;
+#[cfg(target_os = "windows")]
 pub use windows::{
-    error::WindowsError as PlatformError, observer::WindowsWindowObserver as PlatformWindowObserver,
+    error::WindowsError as PlatformError,
+    observer::WindowsWindowObserver as PlatformWindowObserver,
+    process::{is_process_running, process_path},
 };
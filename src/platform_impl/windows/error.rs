@@ -1,3 +1,9 @@
+use windows::Win32::Foundation::{
+    E_ACCESSDENIED, ERROR_ACCESS_DENIED, ERROR_INVALID_PARAMETER, ERROR_INVALID_WINDOW_HANDLE,
+};
+
+use crate::ErrorKind;
+
 /// Represents errors that can occur in the Windows-specific implementation.
 #[derive(Debug, thiserror::Error)]
 pub enum WindowsError {
@@ -8,3 +14,22 @@ pub enum WindowsError {
     #[error("Window getter error: {0}")]
     WindowGetterError(#[from] window_getter::platform_impl::PlatformError),
 }
+
+/// Classifies the error of the Windows API.
+pub(crate) fn error_kind(error: &WindowsError) -> ErrorKind {
+    let WindowsError::WindowGetterError(error) = error else {
+        return ErrorKind::Fatal;
+    };
+    let code = error.code();
+
+    if code == ERROR_INVALID_WINDOW_HANDLE.to_hresult() {
+        ErrorKind::WindowGone
+    } else if code == E_ACCESSDENIED || code == ERROR_ACCESS_DENIED.to_hresult() {
+        ErrorKind::PermissionDenied
+    } else if code == ERROR_INVALID_PARAMETER.to_hresult() {
+        // `OpenProcess` fails with it when the process has exited.
+        ErrorKind::ProcessNotFound
+    } else {
+        ErrorKind::Fatal
+    }
+}
@@ -1,53 +1,335 @@
-use window_getter::{WindowId, platform_impl::PlatformWindow};
+use std::collections::{HashMap, HashSet};
+
+use window_getter::{
+    WindowId,
+    platform_impl::{PlatformWindow, get_windows},
+};
+use windows::Win32::{Foundation::HWND, UI::WindowsAndMessaging::GetForegroundWindow};
 use wineventhook::{
     AccessibleObjectId, MaybeKnown, ObjectWindowEvent, SystemWindowEvent, WindowEvent,
     WindowEventType,
 };
 
 use crate::{
-    Event, EventFilter, EventTx, MaybeWindowAvailable, Window,
-    platform_impl::PlatformError,
-    window::{Position, Size},
+    Event, EventError, EventFilter, EventSender, MaybeWindowAvailable, Monitor, ObserverEvent,
+    Window, WindowMatcher, coalesce,
+    error::ErrorRateLimiter,
+    monitor::{self, DisplayTracker},
+    platform_impl::{
+        PlatformError,
+        windows::{error::WindowsError, window_state},
+    },
+    window::{Position, Rect, Size},
 };
 
+/// The states of a window that are reported by comparing them with the previous ones.
+#[derive(Debug, Default, Clone, Copy)]
+struct WindowState {
+    maximized: bool,
+    fullscreen: bool,
+}
+
+/// The kind of the move-size loop, which is known after the first change of the window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MoveSizeKind {
+    Move,
+    Resize,
+}
+
+/// The move-size loop of the window that the user is moving or resizing.
+#[derive(Debug, Clone, Copy)]
+struct MoveSize {
+    window: PlatformWindow,
+    start: Rect,
+    kind: Option<MoveSizeKind>,
+}
+
 #[derive(Debug, Default, Clone)]
 struct EventInterpreterState {
     foreground: Option<PlatformWindow>,
     previous_pos: Option<Position>,
     previous_size: Option<Size>,
+    /// The states of the windows keyed by the address of their handles.
+    window_states: HashMap<isize, WindowState>,
+    /// The last known titles of the windows keyed like `window_states`.
+    titles: HashMap<isize, String>,
+    /// The last known displays of the windows keyed like `window_states`.
+    monitors: HashMap<isize, Monitor>,
+    move_size: Option<MoveSize>,
+    /// The last known visible top-level windows from the front to the back.
+    stacking: Vec<isize>,
+    /// The top-level windows of the process that are known to exist, keyed like `window_states`.
+    /// They are needed to tell whether a destroyed window belonged to the process.
+    windows: HashSet<isize>,
 }
 
 /// Structs for conversion between a [`WindowEvent`] and a library-specific [`Event`].
 pub struct EventInterpreter {
     pid: u32,
-    event_tx: EventTx,
+    /// It becomes `None` after the target window is closed so that the channel can be closed.
+    event_tx: Option<EventSender>,
     event_filter: EventFilter,
+    /// The only window that the interpreter dispatches events of.
+    target: Option<PlatformWindow>,
+    /// The windows that the interpreter dispatches events of.
+    matcher: Option<WindowMatcher>,
     state: EventInterpreterState,
+    /// It is `None` if no display event is observed.
+    displays: Option<DisplayTracker>,
+    errors: ErrorRateLimiter,
 }
 
 impl EventInterpreter {
-    pub fn new(pid: u32, event_tx: EventTx, event_filter: EventFilter) -> Self {
-        Self {
+    pub fn new(
+        pid: u32,
+        event_tx: EventSender,
+        event_filter: EventFilter,
+        target: Option<PlatformWindow>,
+        matcher: Option<WindowMatcher>,
+    ) -> Self {
+        let mut interpreter = Self {
             pid,
-            event_tx,
+            event_tx: Some(if event_filter.title_changed {
+                coalesce::throttle_titles(event_tx)
+            } else {
+                event_tx
+            }),
             event_filter,
+            target,
+            matcher,
             state: Default::default(),
+            displays: (event_filter.display_added
+                || event_filter.display_removed
+                || event_filter.display_changed)
+                .then(DisplayTracker::new),
+            errors: ErrorRateLimiter::default(),
+        };
+
+        // The states of the existing windows must be known, otherwise their first changes
+        // can't be told from the states they already have.
+        if (interpreter.observes_window_state()
+            || event_filter.title_changed
+            || event_filter.monitor_changed
+            || event_filter.closed)
+            && let Ok(windows) = get_windows()
+        {
+            let monitors = Monitor::all();
+
+            for window in windows.into_iter().map(|w| w.into_platform_window()) {
+                if window.owner_pid().is_ok_and(|pid| pid == interpreter.pid) {
+                    interpreter.state.windows.insert(window.hwnd().0 as isize);
+                    let _ = interpreter.update_window_state(window);
+                    let _ = interpreter.update_title(window);
+
+                    if let Ok(bounds) = window.visible_bounds()
+                        && let Some(monitor) = monitor::dominant(monitors.clone(), &bounds.into())
+                    {
+                        interpreter
+                            .state
+                            .monitors
+                            .insert(window.hwnd().0 as isize, monitor);
+                    }
+                }
+            }
+        }
+
+        if event_filter.stacking_changed {
+            interpreter.state.stacking = stacking_order(pid);
         }
+
+        interpreter
     }
 
-    fn dispatch(&self, window: Option<PlatformWindow>, event: Event) {
-        if self.event_filter.should_dispatch(&event) {
-            let payload = if let Some(window) = window {
-                MaybeWindowAvailable::Available {
-                    window: Window::new(window),
-                    event,
+    fn observes_window_state(&self) -> bool {
+        self.event_filter.maximized
+            || self.event_filter.unmaximized
+            || self.event_filter.entered_fullscreen
+            || self.event_filter.exited_fullscreen
+    }
+
+    fn is_target(&self, window: Option<&PlatformWindow>, event: &Event) -> bool {
+        let Some(target) = self.target else {
+            return true;
+        };
+
+        match (window, event) {
+            (Some(window), _) => *window == target,
+            (None, Event::Closed { window_id }) => *window_id == WindowId::new(target.hwnd()),
+            _ => true,
+        }
+    }
+
+    fn dispatch(&mut self, window: Option<PlatformWindow>, event: Event) {
+        self.dispatch_payload(window, event, false);
+    }
+
+    fn dispatch_initial_state(&mut self, window: PlatformWindow, event: Event) {
+        self.dispatch_payload(Some(window), event, true);
+    }
+
+    fn dispatch_payload(
+        &mut self,
+        window: Option<PlatformWindow>,
+        event: Event,
+        initial_state: bool,
+    ) {
+        if !self.is_target(window.as_ref(), &event) {
+            return;
+        }
+
+        // The properties of the window are queried before the `Window` is sent.
+        if let (Some(matcher), Some(window)) = (&self.matcher, &window)
+            && !matcher.matches(window)
+        {
+            return;
+        }
+
+        let target_closed = self.target.is_some() && matches!(event, Event::Closed { .. });
+
+        if let Some(event_tx) = &self.event_tx
+            && self.event_filter.should_dispatch(&event)
+        {
+            let payload = match window.map(Window::new) {
+                Some(window) if initial_state => {
+                    MaybeWindowAvailable::InitialState { window, event }
                 }
-            } else {
-                MaybeWindowAvailable::NotAvailable { event }
+                Some(window) => MaybeWindowAvailable::Available { window, event },
+                None => MaybeWindowAvailable::NotAvailable { event },
             };
 
-            let _ = self.event_tx.send(Ok(payload));
+            let _ = event_tx.send(Ok(payload));
+        }
+
+        if target_closed {
+            // Nothing will happen to the window anymore, so the stream is ended.
+            self.event_tx = None;
+        }
+    }
+
+    /// Returns `true` if the target window has been closed and no events are sent anymore.
+    pub fn is_closed(&self) -> bool {
+        self.event_tx.is_none()
+    }
+
+    /// Dispatches [`Event::Lifecycle`].
+    pub fn dispatch_lifecycle(&mut self, event: ObserverEvent) {
+        self.dispatch(None, Event::Lifecycle { event });
+    }
+
+    /// Sends the error unless the same one was sent just before.
+    fn send_error(&mut self, error: EventError) {
+        if let Some(event_tx) = &self.event_tx
+            && let Some(error) = self.errors.check(error)
+        {
+            let _ = event_tx.send(Err(error));
+        }
+    }
+
+    /// Sends the events that describe the current state of the windows
+    /// followed by [`Event::InitialStateCompleted`].
+    pub fn send_initial_state(&mut self) {
+        if let Err(e) = self.dispatch_initial_state_events() {
+            self.send_error(EventError::new("sending the initial state", e));
+        }
+
+        if let Some(event_tx) = &self.event_tx {
+            let _ = event_tx.send(Ok(MaybeWindowAvailable::NotAvailable {
+                event: Event::InitialStateCompleted,
+            }));
+        }
+    }
+
+    fn dispatch_initial_state_events(&mut self) -> Result<(), PlatformError> {
+        let windows = match get_windows() {
+            Ok(windows) => windows,
+            Err(window_getter::Error::NoWindowEnvironment) => return Ok(()),
+            Err(
+                window_getter::Error::PermissionDenied(e)
+                | window_getter::Error::PlatformSpecificError(e),
+            ) => return Err(WindowsError::from(e)),
+        };
+
+        for window in windows.into_iter().map(|w| w.into_platform_window()) {
+            // The hidden top-level windows are mostly internal ones that the user never sees.
+            let minimized = window_state::is_minimized(window.hwnd());
+            if !(minimized || window_state::is_visible(window.hwnd()))
+                || window.owner_pid()? != self.pid
+            {
+                continue;
+            }
+
+            self.dispatch_initial_state(window, Event::Created);
+            if minimized {
+                self.dispatch_initial_state(window, Event::Hidden);
+            } else {
+                self.dispatch_initial_state(window, Event::Showed);
+            }
+
+            if window.is_foreground() {
+                self.dispatch_initial_state(window, Event::Focused);
+            }
+        }
+
+        // The foreground window has been reported, so it must be backgrounded
+        // when the foreground moves.
+        if self.state.foreground.is_none() {
+            let hwnd = unsafe { GetForegroundWindow() };
+            self.state.foreground = (!hwnd.is_invalid()).then(|| PlatformWindow::new(hwnd));
         }
+
+        Ok(())
+    }
+
+    /// Updates the maximized and fullscreen states of the window
+    /// and returns the events for their changes.
+    fn update_window_state(&mut self, window: PlatformWindow) -> Result<Vec<Event>, PlatformError> {
+        let hwnd = window.hwnd();
+
+        // The states are kept while the window is minimized,
+        // so that restoring the window is not reported as a change of them.
+        if window_state::is_minimized(hwnd) {
+            return Ok(Vec::new());
+        }
+
+        let current = WindowState {
+            maximized: window_state::is_maximized(hwnd),
+            fullscreen: window_state::is_fullscreen(hwnd)?,
+        };
+        let previous = self
+            .state
+            .window_states
+            .insert(hwnd.0 as isize, current)
+            .unwrap_or_default();
+
+        let mut events = Vec::new();
+        match (previous.maximized, current.maximized) {
+            (false, true) => events.push(Event::Maximized),
+            (true, false) => events.push(Event::Unmaximized),
+            _ => {}
+        }
+        match (previous.fullscreen, current.fullscreen) {
+            (false, true) => events.push(Event::EnteredFullscreen),
+            (true, false) => events.push(Event::ExitedFullscreen),
+            _ => {}
+        }
+
+        Ok(events)
+    }
+
+    /// Updates the title of the window and returns the previous one if it is known.
+    /// It returns `None` if the title is not changed.
+    fn update_title(
+        &mut self,
+        window: PlatformWindow,
+    ) -> Result<Option<(Option<String>, String)>, PlatformError> {
+        let new = window.title()?.unwrap_or_default();
+        let old = self
+            .state
+            .titles
+            .insert(window.hwnd().0 as isize, new.clone());
+
+        // Some applications set the same title repeatedly, for example on every redraw.
+        Ok((old.as_ref() != Some(&new)).then_some((old, new)))
     }
 
     fn on_system_foreground_event(&mut self, window: PlatformWindow) -> Result<(), PlatformError> {
@@ -75,6 +357,35 @@ impl EventInterpreter {
                 self.dispatch(Some(window), Event::Foregrounded);
                 self.dispatch(Some(window), Event::Focused);
             }
+            SystemWindowEvent::MinimizeStart => self.dispatch(Some(window), Event::Minimized),
+            SystemWindowEvent::MinimizeEnd => self.dispatch(Some(window), Event::Restored),
+            SystemWindowEvent::MoveSizeStart => {
+                // Whether the window is moved or resized is not known yet.
+                self.state.move_size = window.visible_bounds().ok().map(|bounds| MoveSize {
+                    window,
+                    start: bounds.into(),
+                    kind: None,
+                });
+            }
+            SystemWindowEvent::MoveSizeEnd => {
+                let Some(MoveSize {
+                    kind: Some(kind), ..
+                }) = self
+                    .state
+                    .move_size
+                    .take()
+                    .filter(|move_size| move_size.window == window)
+                else {
+                    return Ok(());
+                };
+                let bounds = window.visible_bounds()?.into();
+
+                let event = match kind {
+                    MoveSizeKind::Move => Event::MoveEnded { bounds },
+                    MoveSizeKind::Resize => Event::ResizeEnded { bounds },
+                };
+                self.dispatch(Some(window), event);
+            }
             _ => return Ok(()),
         };
 
@@ -88,14 +399,34 @@ impl EventInterpreter {
     ) -> Result<(), PlatformError> {
         match event {
             ObjectWindowEvent::LocationChange => {
+                // The window may have been moved by a change of the displays.
+                self.check_displays(true);
+
                 let Ok(visible_bounds) = window.visible_bounds() else {
                     return Ok(());
                 };
 
                 // Check if the position has changed.
                 // `LocationChange` can be triggered by both position and size changes.
-                let current_pos: Position = visible_bounds.clone().into();
-                let previous_pos = self.state.previous_pos.replace(current_pos.clone());
+                let bounds: Rect = visible_bounds.into();
+
+                // The start is dispatched on the first change, so it precedes the changes.
+                if let Some(move_size) = &mut self.state.move_size
+                    && move_size.window == window
+                    && move_size.kind.is_none()
+                    && move_size.start != bounds
+                {
+                    let (kind, event) = if move_size.start.size() == bounds.size() {
+                        (MoveSizeKind::Move, Event::MoveStarted)
+                    } else {
+                        (MoveSizeKind::Resize, Event::ResizeStarted)
+                    };
+                    move_size.kind = Some(kind);
+                    self.dispatch(Some(window), event);
+                }
+
+                let current_pos = bounds.position();
+                let previous_pos = self.state.previous_pos.replace(current_pos);
 
                 if is_hidden_pos(current_pos) {
                     self.dispatch(Some(window), Event::Hidden);
@@ -104,39 +435,93 @@ impl EventInterpreter {
                 } else if previous_pos.is_none()
                     || previous_pos.is_some_and(|previous_pos| previous_pos != current_pos)
                 {
-                    self.dispatch(Some(window), Event::Moved);
+                    self.dispatch(Some(window), Event::Moved { bounds });
                 }
 
                 // Check if the size has changed.
-                let current_size: Size = visible_bounds.into();
-                let previous_size = self.state.previous_size.replace(current_size.clone());
+                let current_size = bounds.size();
+                let previous_size = self.state.previous_size.replace(current_size);
 
                 if previous_size.is_none()
                     || previous_size.is_some_and(|previous_size| previous_size != current_size)
                 {
-                    self.dispatch(Some(window), Event::Resized);
+                    self.dispatch(Some(window), Event::Resized { bounds });
+                }
+
+                if self.event_filter.monitor_changed
+                    && let Some(current) = Monitor::dominant_for(&bounds)
+                    && let Some(from) = self
+                        .state
+                        .monitors
+                        .insert(window.hwnd().0 as isize, current.clone())
+                    && from.id != current.id
+                {
+                    self.dispatch(
+                        Some(window),
+                        Event::MonitorChanged {
+                            from: Box::new(from),
+                            to: Box::new(current),
+                        },
+                    );
+                }
+
+                if self.observes_window_state() {
+                    for event in self.update_window_state(window)? {
+                        self.dispatch(Some(window), event);
+                    }
                 }
             }
             ObjectWindowEvent::Create => self.dispatch(Some(window), Event::Created),
             ObjectWindowEvent::Hide => self.dispatch(Some(window), Event::Hidden),
             ObjectWindowEvent::Show => self.dispatch(Some(window), Event::Showed),
-            ObjectWindowEvent::Destroy => self.dispatch(
-                None,
-                Event::Closed {
-                    window_id: WindowId::new(window.hwnd()),
-                },
-            ),
+            ObjectWindowEvent::NameChange => {
+                // The event is also sent for the controls in the window.
+                if window_state::is_top_level(window.hwnd())
+                    && let Some((old, new)) = self.update_title(window)?
+                {
+                    self.dispatch(Some(window), Event::TitleChanged { old, new });
+                }
+            }
             _ => return Ok(()),
         };
 
         Ok(())
     }
 
+    /// Dispatches the changes of the displays since the last check.
+    fn check_displays(&mut self, force: bool) {
+        let Some(displays) = &mut self.displays else {
+            return;
+        };
+
+        for event in displays.check(force) {
+            self.dispatch(None, event);
+        }
+    }
+
+    /// Dispatches [`Event::StackingChanged`] if the order of the windows was changed.
+    fn update_stacking(&mut self) {
+        let stacking = stacking_order(self.pid);
+
+        if stacking != self.state.stacking {
+            let windows = stacking
+                .iter()
+                .map(|hwnd| Window::new(PlatformWindow::new(HWND(*hwnd as _))))
+                .collect();
+            self.state.stacking = stacking;
+            self.dispatch(None, Event::StackingChanged { windows });
+        }
+    }
+
     fn dispatch_wineventhook_event(
         &mut self,
         window: PlatformWindow,
         event: WindowEvent,
     ) -> Result<(), PlatformError> {
+        // The events of all windows are received, so the displays are checked periodically
+        // even if the observed process is idle.
+        self.check_displays(false);
+
         if matches!(
             event.event_type(),
             WindowEventType::System(MaybeKnown::Known(SystemWindowEvent::Foreground))
@@ -144,10 +529,18 @@ impl EventInterpreter {
             self.on_system_foreground_event(window)?;
         }
 
+        if self.target.is_some_and(|target| target != window) {
+            return Ok(());
+        }
+
         if window.owner_pid()? != self.pid {
             return Ok(());
         }
 
+        if window_state::is_top_level(window.hwnd()) {
+            self.state.windows.insert(window.hwnd().0 as isize);
+        }
+
         match event.event_type() {
             WindowEventType::System(MaybeKnown::Known(event)) => {
                 self.on_system_event(window, event)?;
@@ -163,13 +556,97 @@ impl EventInterpreter {
             _ => {}
         }
 
+        if self.event_filter.stacking_changed && changes_stacking(&event) {
+            self.update_stacking();
+        }
+
         Ok(())
     }
 
+    /// Dispatches [`Event::Closed`] if the destroyed window is the target
+    /// or one of the known windows of the process.
+    ///
+    /// The window is already gone when its destruction is received,
+    /// so it is matched by its handle instead of its properties.
+    pub fn interpret_destroyed_window(&mut self, hwnd: HWND) {
+        let key = hwnd.0 as isize;
+        let known = self.state.windows.remove(&key);
+
+        if !self.target.map_or(known, |target| target.hwnd() == hwnd) {
+            return;
+        }
+
+        self.state.window_states.remove(&key);
+        self.state.titles.remove(&key);
+        self.state.monitors.remove(&key);
+        self.dispatch(
+            None,
+            Event::Closed {
+                window_id: WindowId::new(hwnd),
+            },
+        );
+
+        if self.event_filter.stacking_changed {
+            self.update_stacking();
+        }
+    }
+
     pub fn interpret_wineventhook_event(&mut self, window: PlatformWindow, event: WindowEvent) {
+        let window_id = WindowId::new(window.hwnd());
+        let event_type = event.event_type();
+
         if let Err(e) = self.dispatch_wineventhook_event(window, event) {
-            let _ = self.event_tx.send(Err(e));
-        };
+            self.send_error(
+                EventError::new("interpreting an event", e)
+                    .with_window_id(Some(window_id))
+                    .with_raw_event(format!("{event_type:?}")),
+            );
+        }
+    }
+}
+
+/// Checks whether the event reports that a window has been destroyed.
+pub fn is_window_destroyed(event: &WindowEvent) -> bool {
+    matches!(
+        event.event_type(),
+        WindowEventType::Object(MaybeKnown::Known(ObjectWindowEvent::Destroy))
+    ) && matches!(
+        event.object_type(),
+        MaybeKnown::Known(AccessibleObjectId::Window)
+    )
+}
+
+/// Returns the windows of the process from the front to the back keyed by their handles.
+fn stacking_order(pid: u32) -> Vec<isize> {
+    window_state::stacking_order(pid)
+        .into_iter()
+        .map(|hwnd| hwnd.0 as isize)
+        .collect()
+}
+
+/// Checks whether the event can change the stacking order of the windows.
+fn changes_stacking(event: &WindowEvent) -> bool {
+    match event.event_type() {
+        WindowEventType::System(MaybeKnown::Known(event)) => matches!(
+            event,
+            SystemWindowEvent::Foreground
+                | SystemWindowEvent::MinimizeStart
+                | SystemWindowEvent::MinimizeEnd
+        ),
+        WindowEventType::Object(MaybeKnown::Known(inner_event)) => {
+            matches!(
+                event.object_type(),
+                MaybeKnown::Known(AccessibleObjectId::Window)
+            ) && matches!(
+                inner_event,
+                ObjectWindowEvent::Create
+                    | ObjectWindowEvent::Destroy
+                    | ObjectWindowEvent::Show
+                    | ObjectWindowEvent::Hide
+                    | ObjectWindowEvent::Reorder
+            )
+        }
+        _ => false,
     }
 }
 
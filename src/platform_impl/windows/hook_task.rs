@@ -1,46 +1,236 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, RecvTimeoutError},
+    },
+    thread::JoinHandle,
+    time::Duration,
+};
+
 use tokio::sync::mpsc::UnboundedReceiver;
-use window_getter::platform_impl::get_window;
+use window_getter::platform_impl::{PlatformWindow, get_window};
 use windows::Win32::Foundation;
 use wineventhook::{WindowEventHook, raw_event};
 
-use super::{error::WindowsError, event_interpreter::EventInterpreter};
-use crate::{EventFilter, EventTx};
+use super::{
+    error::WindowsError,
+    event_interpreter::{EventInterpreter, is_window_destroyed},
+    process::is_process_running,
+};
+use crate::{
+    DegradedReason, Event, EventFilter, EventSender, MaybeWindowAvailable, ObserverEvent,
+    ObserverOptions,
+};
+
+/// How often the process is checked whether it is still running.
+const PROCESS_CHECK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The hook and the threads that handle its events.
+pub struct HookTask {
+    hook: WindowEventHook,
+    handler: JoinHandle<()>,
+    /// It tells the handler that the hook is removed on purpose.
+    stopping: Arc<AtomicBool>,
+    /// It is `None` if [`ObserverEvent::TargetExited`] is not observed.
+    exit_watcher: Option<(mpsc::Sender<()>, JoinHandle<()>)>,
+}
+
+impl HookTask {
+    pub fn hook(&self) -> &WindowEventHook {
+        &self.hook
+    }
+
+    /// Returns `false` if the handler has ended, which means that no events are received.
+    pub fn is_running(&self) -> bool {
+        !self.handler.is_finished()
+    }
+
+    /// Removes the hook and waits for the handler to handle the remaining events.
+    pub async fn stop(self) -> Result<(), WindowsError> {
+        if let Some((stop_tx, handle)) = self.exit_watcher {
+            let _ = stop_tx.send(());
+            let _ = handle.join();
+        }
+
+        // The hook thread has already ended, so it can't be unhooked.
+        if self.handler.is_finished() {
+            return Ok(());
+        }
+
+        self.stopping.store(true, Ordering::Release);
+        self.hook.unhook().await?;
+
+        // The handler ends soon because the hook thread has dropped the sender of the events.
+        let _ = self.handler.join();
+
+        Ok(())
+    }
+}
 
 fn handle_events(
     mut rx: UnboundedReceiver<wineventhook::WindowEvent>,
     mut event_interpreter: EventInterpreter,
+    stopping: &AtomicBool,
+    mut exit_watcher_stop: Option<mpsc::Sender<()>>,
 ) {
     while let Some(event) = rx.blocking_recv() {
         if let Some(hwnd) = event.window_handle() {
             let hwnd = Foundation::HWND(hwnd.as_ptr() as _);
+
+            // The handle is no longer valid when the destruction is received.
+            if is_window_destroyed(&event) {
+                let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                    event_interpreter.interpret_destroyed_window(hwnd);
+                }));
+                stop_exit_watcher_if_closed(&event_interpreter, &mut exit_watcher_stop);
+                continue;
+            }
+
             let Some(window) = get_window(hwnd).map(|w| w.into_platform_window()) else {
                 // If hwnd is not valid window, continue;
                 continue;
             };
 
-            event_interpreter.interpret_wineventhook_event(window, event);
+            // A panic must not end this thread, otherwise the observer would stop receiving events.
+            let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                event_interpreter.interpret_wineventhook_event(window, event);
+            }));
         }
     }
+
+    // The channel is closed when the hook thread ends, which is unexpected unless it is stopped.
+    event_interpreter.dispatch_lifecycle(if stopping.load(Ordering::Acquire) {
+        ObserverEvent::Stopped
+    } else {
+        ObserverEvent::Degraded {
+            reason: DegradedReason::BackendEnded,
+        }
+    });
 }
 
-pub async fn make_wineventhook_task(
+/// Stops the exit watcher once the target window has been closed,
+/// because its sender would keep the channel open.
+fn stop_exit_watcher_if_closed(
+    event_interpreter: &EventInterpreter,
+    exit_watcher_stop: &mut Option<mpsc::Sender<()>>,
+) {
+    if event_interpreter.is_closed()
+        && let Some(stop_tx) = exit_watcher_stop.take()
+    {
+        let _ = stop_tx.send(());
+    }
+}
+
+/// Sends [`ObserverEvent::TargetExited`] when the process has exited,
+/// until a message is received from `stop_rx`.
+fn watch_process_exit(
     pid: u32,
-    event_tx: EventTx,
+    event_tx: EventSender,
     event_filter: EventFilter,
-) -> Result<WindowEventHook, WindowsError> {
+    stop_rx: mpsc::Receiver<()>,
+) {
+    while let Err(RecvTimeoutError::Timeout) = stop_rx.recv_timeout(PROCESS_CHECK_INTERVAL) {
+        if !is_process_running(pid) {
+            let event = Event::Lifecycle {
+                event: ObserverEvent::TargetExited { pid },
+            };
+
+            if event_filter.should_dispatch(&event) {
+                let _ = event_tx.send(Ok(MaybeWindowAvailable::NotAvailable { event }));
+            }
+            return;
+        }
+    }
+}
+
+pub async fn make_wineventhook_task(
+    pid: u32,
+    event_tx: EventSender,
+    options: ObserverOptions,
+    target: Option<PlatformWindow>,
+) -> Result<HookTask, WindowsError> {
     let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
     let hook = WindowEventHook::hook(
         wineventhook::EventFilter::default()
-            .events(raw_event::SYSTEM_START..raw_event::OBJECT_LOCATIONCHANGE),
+            .events(raw_event::SYSTEM_START..raw_event::OBJECT_NAMECHANGE),
         tx,
     )
     .await?;
 
-    std::thread::spawn(move || {
-        let event_interpreter = EventInterpreter::new(pid, event_tx, event_filter);
+    let exit_watcher = options.event_filter.lifecycle.then(|| {
+        let (stop_tx, stop_rx) = mpsc::channel();
+        let event_tx = event_tx.clone();
+        let handle = std::thread::spawn(move || {
+            watch_process_exit(pid, event_tx, options.event_filter, stop_rx);
+        });
+
+        (stop_tx, handle)
+    });
+
+    let stopping = Arc::new(AtomicBool::new(false));
+    let handler = std::thread::spawn({
+        let stopping = Arc::clone(&stopping);
+        let exit_watcher_stop = exit_watcher.as_ref().map(|(stop_tx, _)| stop_tx.clone());
+
+        move || {
+            let mut event_interpreter =
+                EventInterpreter::new(pid, event_tx, options.event_filter, target, options.matcher);
+            event_interpreter.dispatch_lifecycle(ObserverEvent::Started);
 
-        handle_events(rx, event_interpreter);
+            // The events received by the hook meanwhile are queued in `rx`,
+            // so the snapshot always precedes them.
+            if options.initial_snapshot {
+                event_interpreter.send_initial_state();
+            }
+
+            handle_events(rx, event_interpreter, &stopping, exit_watcher_stop);
+        }
     });
 
-    Ok(hook)
+    Ok(HookTask {
+        hook,
+        handler,
+        stopping,
+        exit_watcher,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    #[test]
+    fn closing_the_target_ends_the_stream() {
+        let (event_tx, event_rx) = mpsc::channel();
+        let event_tx = EventSender::from(event_tx);
+        let pid = std::process::id();
+        let target = PlatformWindow::new(Foundation::HWND(1 as _));
+
+        let (stop_tx, stop_rx) = mpsc::channel();
+        let watcher = thread::spawn({
+            let event_tx = event_tx.clone();
+            move || watch_process_exit(pid, event_tx, EventFilter::all(), stop_rx)
+        });
+        let mut exit_watcher_stop = Some(stop_tx);
+
+        let mut event_interpreter =
+            EventInterpreter::new(pid, event_tx, EventFilter::all(), Some(target), None);
+        event_interpreter.interpret_destroyed_window(target.hwnd());
+        stop_exit_watcher_if_closed(&event_interpreter, &mut exit_watcher_stop);
+        watcher.join().unwrap();
+
+        assert!(matches!(
+            event_rx.recv(),
+            Ok(Ok(MaybeWindowAvailable::NotAvailable {
+                event: Event::Closed { .. }
+            }))
+        ));
+
+        // The interpreter is kept alive, but the stream has ended.
+        assert!(event_rx.recv().is_err());
+        drop(event_interpreter);
+    }
 }
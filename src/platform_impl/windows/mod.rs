@@ -3,4 +3,9 @@
 pub mod error;
 mod event_interpreter;
 mod hook_task;
+pub(crate) mod monitor;
 pub mod observer;
+pub mod process;
+mod properties;
+pub(crate) mod window_control;
+pub(crate) mod window_state;
@@ -0,0 +1,92 @@
+//! Enumeration of the displays through GDI.
+
+use windows::{
+    Win32::{
+        Foundation::{LPARAM, RECT},
+        Graphics::Gdi::{
+            EnumDisplayMonitors, GetMonitorInfoW, HDC, HMONITOR, MONITORINFO, MONITORINFOEXW,
+        },
+        UI::{
+            HiDpi::{GetDpiForMonitor, MDT_EFFECTIVE_DPI},
+            WindowsAndMessaging::{MONITORINFOF_PRIMARY, USER_DEFAULT_SCREEN_DPI},
+        },
+    },
+    core::BOOL,
+};
+
+use crate::{
+    monitor::{Monitor, MonitorId},
+    window::Rect,
+};
+
+unsafe extern "system" fn enum_monitors_callback(
+    monitor: HMONITOR,
+    _: HDC,
+    _: *mut RECT,
+    lparam: LPARAM,
+) -> BOOL {
+    let monitors = unsafe { &mut *(lparam.0 as *mut Vec<HMONITOR>) };
+    monitors.push(monitor);
+
+    BOOL::from(true)
+}
+
+#[inline]
+fn to_rect(rect: RECT) -> Rect {
+    Rect {
+        x: rect.left as _,
+        y: rect.top as _,
+        width: (rect.right - rect.left) as _,
+        height: (rect.bottom - rect.top) as _,
+    }
+}
+
+fn monitor(handle: HMONITOR) -> Option<Monitor> {
+    let mut info = MONITORINFOEXW::default();
+    info.monitorInfo.cbSize = size_of::<MONITORINFOEXW>() as u32;
+
+    if !unsafe { GetMonitorInfoW(handle, &mut info as *mut MONITORINFOEXW as *mut MONITORINFO) }
+        .as_bool()
+    {
+        return None;
+    }
+
+    let (mut dpi_x, mut dpi_y) = (0, 0);
+    let scale_factor =
+        match unsafe { GetDpiForMonitor(handle, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y) } {
+            Ok(()) => dpi_x as f64 / USER_DEFAULT_SCREEN_DPI as f64,
+            Err(_) => 1.,
+        };
+
+    let name_length = info
+        .szDevice
+        .iter()
+        .position(|&c| c == 0)
+        .unwrap_or(info.szDevice.len());
+
+    Some(Monitor {
+        id: MonitorId(handle.0 as u64),
+        name: Some(String::from_utf16_lossy(&info.szDevice[..name_length])),
+        bounds: to_rect(info.monitorInfo.rcMonitor),
+        work_area: to_rect(info.monitorInfo.rcWork),
+        scale_factor,
+        is_primary: info.monitorInfo.dwFlags & MONITORINFOF_PRIMARY != 0,
+    })
+}
+
+/// Returns the displays attached to the desktop.
+pub(crate) fn monitors() -> Vec<Monitor> {
+    let mut handles = Vec::<HMONITOR>::new();
+
+    // SAFETY: `Vec` should not be used during enumeration because it is used by mutable reference.
+    let _ = unsafe {
+        EnumDisplayMonitors(
+            None,
+            None,
+            Some(enum_monitors_callback),
+            LPARAM(&mut handles as *mut Vec<HMONITOR> as _),
+        )
+    };
+
+    handles.into_iter().filter_map(monitor).collect()
+}
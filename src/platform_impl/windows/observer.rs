@@ -1,42 +1,95 @@
+use window_getter::{WindowId, platform_impl::get_window};
+use windows::Win32::Foundation::HWND;
 use wineventhook::WindowEventHook;
 
-use crate::{Error, EventTx};
+use super::error::WindowsError;
+use crate::{Capabilities, Error, EventSender, ObserverOptions};
 
-use super::hook_task::make_wineventhook_task;
+use super::hook_task::{HookTask, make_wineventhook_task};
 
 /// Observes window events on the Windows platform by using [wineventhook].
 pub struct WindowsWindowObserver {
-    hook: WindowEventHook,
+    task: HookTask,
 }
 
 impl WindowsWindowObserver {
     /// Starts observing window events for a specific process ID.
     pub async fn start(
         pid: u32,
-        event_tx: EventTx,
+        event_tx: EventSender,
         event_filter: crate::EventFilter,
+    ) -> Result<Self, Error> {
+        Self::start_with_options(pid, event_tx, event_filter.into()).await
+    }
+
+    pub(crate) async fn start_with_options(
+        pid: u32,
+        event_tx: EventSender,
+        options: ObserverOptions,
     ) -> Result<Self, Error> {
         if pid == 0 {
             return Err(Error::InvalidProcessId(pid));
         }
 
-        let hook = make_wineventhook_task(pid, event_tx, event_filter).await?;
+        let task = make_wineventhook_task(pid, event_tx, options, None).await?;
+
+        Ok(Self { task })
+    }
+
+    /// Starts observing window events only for the window that has the given handle.
+    ///
+    /// The hook receives the events of all windows,
+    /// so the events of the other windows are discarded by the interpreter.
+    pub async fn start_for_window(
+        hwnd: HWND,
+        event_tx: EventSender,
+        event_filter: crate::EventFilter,
+    ) -> Result<Self, Error> {
+        Self::start_for_window_with_options(hwnd, event_tx, event_filter.into()).await
+    }
+
+    pub(crate) async fn start_for_window_with_options(
+        hwnd: HWND,
+        event_tx: EventSender,
+        options: ObserverOptions,
+    ) -> Result<Self, Error> {
+        let window = get_window(hwnd)
+            .ok_or(Error::WindowNotFound(WindowId::new(hwnd)))?
+            .into_platform_window();
+        let pid = window.owner_pid().map_err(WindowsError::from)?;
 
-        Ok(Self { hook })
+        let task = make_wineventhook_task(pid, event_tx, options, Some(window)).await?;
+
+        Ok(Self { task })
+    }
+
+    /// Returns the features supported by the Windows backend.
+    pub fn capabilities() -> Capabilities {
+        Capabilities {
+            events: crate::EventFilter {
+                process_attached: false,
+                process_detached: false,
+                ..crate::EventFilter::all()
+            },
+            window_target: true,
+        }
     }
 
     /// Stops observing window events.
+    /// The events received before it are sent before [`ObserverEvent::Stopped`](crate::ObserverEvent::Stopped).
     pub async fn stop(self) -> Result<(), Error> {
-        self.hook
-            .unhook()
-            .await
-            .map_err(super::error::WindowsError::from)?;
+        self.task.stop().await?;
 
         Ok(())
     }
 
+    /// Returns `false` if the hook has ended by itself and no events are received anymore.
+    pub fn is_running(&self) -> bool {
+        self.task.is_running()
+    }
+
     /// Retrieves the underlying [`WindowEventHook`].
     pub fn hook(&self) -> &WindowEventHook {
-        &self.hook
+        self.task.hook()
     }
 }
@@ -0,0 +1,47 @@
+use std::{ffi::OsString, os::windows::ffi::OsStringExt, path::PathBuf};
+
+use windows::{
+    Win32::{
+        Foundation::{CloseHandle, MAX_PATH, STILL_ACTIVE},
+        System::Threading::{
+            GetExitCodeProcess, OpenProcess, PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION,
+            QueryFullProcessImageNameW,
+        },
+    },
+    core::PWSTR,
+};
+
+/// Checks whether the process with the given ID is still running.
+pub fn is_process_running(pid: u32) -> bool {
+    let Ok(handle) = (unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) }) else {
+        return false;
+    };
+
+    let mut exit_code = 0;
+    let result = unsafe { GetExitCodeProcess(handle, &mut exit_code) };
+    let _ = unsafe { CloseHandle(handle) };
+
+    result.is_ok() && exit_code == STILL_ACTIVE.0 as u32
+}
+
+/// Retrieves the path of the executable of the process with the given ID.
+pub fn process_path(pid: u32) -> Option<PathBuf> {
+    let handle = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) }.ok()?;
+
+    // Long paths are allowed, so the buffer is larger than `MAX_PATH`.
+    let mut buffer = vec![0u16; MAX_PATH as usize * 4];
+    let mut length = buffer.len() as u32;
+    let result = unsafe {
+        QueryFullProcessImageNameW(
+            handle,
+            PROCESS_NAME_WIN32,
+            PWSTR(buffer.as_mut_ptr()),
+            &mut length,
+        )
+    };
+    let _ = unsafe { CloseHandle(handle) };
+
+    result.ok()?;
+    buffer.truncate(length as usize);
+    Some(OsString::from_wide(&buffer).into())
+}
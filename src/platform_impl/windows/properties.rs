@@ -0,0 +1,27 @@
+//! Properties of a window for [`WindowMatcher`](crate::matcher::WindowMatcher).
+
+use window_getter::platform_impl::PlatformWindow;
+
+use super::window_state;
+use crate::{
+    Rect,
+    matcher::{WindowProperties, WindowRole},
+};
+
+impl WindowProperties for PlatformWindow {
+    fn title(&self) -> Option<String> {
+        PlatformWindow::title(self).ok().flatten()
+    }
+
+    fn app_id(&self) -> Option<String> {
+        self.owner_name().ok()
+    }
+
+    fn role(&self) -> Option<WindowRole> {
+        window_state::role(self.hwnd())
+    }
+
+    fn bounds(&self) -> Option<Rect> {
+        self.visible_bounds().ok().map(Rect::from)
+    }
+}
@@ -0,0 +1,97 @@
+//! Actions on a window such as moving or minimizing it.
+
+use window_getter::platform_impl::PlatformWindow;
+use windows::Win32::{
+    Foundation::{HWND, LPARAM, WPARAM},
+    UI::WindowsAndMessaging::{
+        HWND_TOP, PostMessageW, SET_WINDOW_POS_FLAGS, SW_MAXIMIZE, SW_MINIMIZE, SW_RESTORE,
+        SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE, SWP_NOZORDER, SetForegroundWindow, SetWindowPos,
+        ShowWindow, WM_CLOSE,
+    },
+};
+
+use super::{error::WindowsError, window_state};
+use crate::window::{Position, Size};
+
+/// Moves and resizes the window.
+///
+/// The position and the size are the ones of the visible bounds like [`crate::Window::position`],
+/// so the invisible resize borders around the window are added to them.
+pub(crate) fn set_bounds(
+    hwnd: HWND,
+    position: Option<Position>,
+    size: Option<Size>,
+) -> Result<(), WindowsError> {
+    let window = PlatformWindow::new(hwnd);
+    let rect = window.rect()?;
+    let visible = window.extended_frame_bounds()?;
+
+    let (left, top) = (visible.left - rect.left, visible.top - rect.top);
+    let (right, bottom) = (rect.right - visible.right, rect.bottom - visible.bottom);
+
+    let mut flags = SWP_NOZORDER | SWP_NOACTIVATE;
+    let (x, y) = match position {
+        Some(position) => (
+            position.x.round() as i32 - left,
+            position.y.round() as i32 - top,
+        ),
+        None => {
+            flags |= SWP_NOMOVE;
+            (0, 0)
+        }
+    };
+    let (width, height) = match size {
+        Some(size) => (
+            size.width.round() as i32 + left + right,
+            size.height.round() as i32 + top + bottom,
+        ),
+        None => {
+            flags |= SWP_NOSIZE;
+            (0, 0)
+        }
+    };
+
+    unsafe { SetWindowPos(hwnd, None, x, y, width, height, flags) }?;
+
+    Ok(())
+}
+
+/// Brings the window to the top of the z-order without activating it.
+pub(crate) fn raise(hwnd: HWND) -> Result<(), WindowsError> {
+    let flags: SET_WINDOW_POS_FLAGS = SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE;
+    unsafe { SetWindowPos(hwnd, Some(HWND_TOP), 0, 0, 0, 0, flags) }?;
+
+    Ok(())
+}
+
+/// Restores the window if it is minimized and brings it to the foreground.
+pub(crate) fn focus(hwnd: HWND) {
+    if window_state::is_minimized(hwnd) {
+        restore(hwnd);
+    }
+
+    // Windows may refuse it. The window flashes on the taskbar instead in that case.
+    let _ = unsafe { SetForegroundWindow(hwnd) };
+}
+
+/// Minimizes the window to the taskbar.
+pub(crate) fn minimize(hwnd: HWND) {
+    let _ = unsafe { ShowWindow(hwnd, SW_MINIMIZE) };
+}
+
+/// Restores the window from the minimized or maximized state.
+pub(crate) fn restore(hwnd: HWND) {
+    let _ = unsafe { ShowWindow(hwnd, SW_RESTORE) };
+}
+
+/// Maximizes the window.
+pub(crate) fn maximize(hwnd: HWND) {
+    let _ = unsafe { ShowWindow(hwnd, SW_MAXIMIZE) };
+}
+
+/// Asks the window to close as if the user clicked its close button.
+pub(crate) fn close(hwnd: HWND) -> Result<(), WindowsError> {
+    unsafe { PostMessageW(Some(hwnd), WM_CLOSE, WPARAM(0), LPARAM(0)) }?;
+
+    Ok(())
+}
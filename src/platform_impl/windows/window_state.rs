@@ -0,0 +1,135 @@
+//! Queries of the state of a window such as minimized or maximized.
+
+use windows::Win32::{
+    Foundation::{HWND, RECT},
+    Graphics::Gdi::{GetMonitorInfoW, MONITOR_DEFAULTTONULL, MONITORINFO, MonitorFromWindow},
+    UI::HiDpi::GetDpiForWindow,
+    UI::WindowsAndMessaging::{
+        GA_ROOT, GW_HWNDNEXT, GWL_EXSTYLE, GWL_STYLE, GetAncestor, GetClassNameW, GetTopWindow,
+        GetWindow, GetWindowLongPtrW, GetWindowRect, GetWindowThreadProcessId, IsIconic, IsWindow,
+        IsWindowVisible, IsZoomed, USER_DEFAULT_SCREEN_DPI, WINDOW_EX_STYLE, WINDOW_STYLE,
+        WS_CAPTION, WS_EX_DLGMODALFRAME, WS_EX_TOOLWINDOW,
+    },
+};
+
+use super::error::WindowsError;
+use crate::matcher::WindowRole;
+
+/// The class of the dialog boxes created by the dialog functions such as `MessageBox`.
+const DIALOG_CLASS_NAME: &str = "#32770";
+
+/// Checks if the window is a top-level window rather than a control in another window.
+pub(crate) fn is_top_level(hwnd: HWND) -> bool {
+    let root = unsafe { GetAncestor(hwnd, GA_ROOT) };
+    root == hwnd
+}
+
+/// Checks if the window is minimized to the taskbar.
+pub(crate) fn is_minimized(hwnd: HWND) -> bool {
+    unsafe { IsIconic(hwnd) }.as_bool()
+}
+
+/// Checks if the window is maximized.
+pub(crate) fn is_maximized(hwnd: HWND) -> bool {
+    unsafe { IsZoomed(hwnd) }.as_bool()
+}
+
+/// Checks if the window is shown and not minimized.
+pub(crate) fn is_visible(hwnd: HWND) -> bool {
+    unsafe { IsWindowVisible(hwnd) }.as_bool() && !is_minimized(hwnd)
+}
+
+/// Checks if the window covers its whole monitor without being maximized.
+///
+/// Windows has no fullscreen state, so this is how the fullscreen mode of
+/// the games and the video players is usually detected.
+pub(crate) fn is_fullscreen(hwnd: HWND) -> Result<bool, WindowsError> {
+    if !is_visible(hwnd) || is_maximized(hwnd) {
+        return Ok(false);
+    }
+
+    let monitor = unsafe { MonitorFromWindow(hwnd, MONITOR_DEFAULTTONULL) };
+    if monitor.is_invalid() {
+        return Ok(false);
+    }
+
+    let mut info = MONITORINFO {
+        cbSize: size_of::<MONITORINFO>() as u32,
+        ..Default::default()
+    };
+    if !unsafe { GetMonitorInfoW(monitor, &mut info) }.as_bool() {
+        return Err(window_getter::platform_impl::PlatformError::from_thread().into());
+    }
+
+    let mut rect = RECT::default();
+    unsafe { GetWindowRect(hwnd, &mut rect) }?;
+
+    Ok(covers(&rect, &info.rcMonitor))
+}
+
+/// Returns the visible top-level windows of the process from the front to the back.
+/// The minimized windows are included.
+pub(crate) fn stacking_order(pid: u32) -> Vec<HWND> {
+    let mut order = Vec::new();
+    let mut next = unsafe { GetTopWindow(None) };
+
+    while let Ok(hwnd) = next {
+        let mut owner_pid = 0;
+        unsafe { GetWindowThreadProcessId(hwnd, Some(&mut owner_pid)) };
+
+        if owner_pid == pid && unsafe { IsWindowVisible(hwnd) }.as_bool() {
+            order.push(hwnd);
+        }
+
+        next = unsafe { GetWindow(hwnd, GW_HWNDNEXT) };
+    }
+
+    order
+}
+
+/// Returns the number of physical pixels per logical pixel of the window.
+pub(crate) fn scale_factor(hwnd: HWND) -> Result<f64, WindowsError> {
+    let dpi = unsafe { GetDpiForWindow(hwnd) };
+
+    if dpi == 0 {
+        // It fails only when the handle is invalid.
+        return Err(window_getter::platform_impl::PlatformError::from_thread().into());
+    }
+
+    Ok(dpi as f64 / USER_DEFAULT_SCREEN_DPI as f64)
+}
+
+#[inline]
+fn covers(rect: &RECT, area: &RECT) -> bool {
+    rect.left <= area.left
+        && rect.top <= area.top
+        && rect.right >= area.right
+        && rect.bottom >= area.bottom
+}
+
+/// Tells the kind of the window from its styles and its class.
+/// It returns `None` if the window doesn't exist.
+pub(crate) fn role(hwnd: HWND) -> Option<WindowRole> {
+    if !unsafe { IsWindow(Some(hwnd)) }.as_bool() {
+        return None;
+    }
+
+    let style = WINDOW_STYLE(unsafe { GetWindowLongPtrW(hwnd, GWL_STYLE) } as u32);
+    let ex_style = WINDOW_EX_STYLE(unsafe { GetWindowLongPtrW(hwnd, GWL_EXSTYLE) } as u32);
+
+    let mut class_name = [0u16; 256];
+    let length = unsafe { GetClassNameW(hwnd, &mut class_name) };
+    let class_name = String::from_utf16_lossy(&class_name[..length.max(0) as usize]);
+
+    Some(
+        if class_name == DIALOG_CLASS_NAME || ex_style.contains(WS_EX_DLGMODALFRAME) {
+            WindowRole::Dialog
+        } else if ex_style.contains(WS_EX_TOOLWINDOW) {
+            WindowRole::Panel
+        } else if style.contains(WS_CAPTION) {
+            WindowRole::Standard
+        } else {
+            WindowRole::Other
+        },
+    )
+}
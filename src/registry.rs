@@ -0,0 +1,809 @@
+//! A live model of the observed windows.
+//!
+//! A [`WindowRegistry`] keeps the windows, their last known bounds, titles and visibility,
+//! and the focused window up to date from the events of the observer.
+//! It is fed by [`WindowObserverBuilder::registry`](crate::WindowObserverBuilder::registry)
+//! or by a [`RegistrySink`] wrapping a channel.
+
+use std::{
+    sync::{Arc, Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard},
+    time::Instant,
+};
+
+use window_getter::WindowId;
+
+use crate::{
+    Event, EventResult, EventSender, EventSink, MaybeWindowAvailable, Position, Rect, Window,
+    spatial::{self, MOSTLY_COVERED_THRESHOLD, Region},
+};
+
+/// The state of a window known by a [`WindowRegistry`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowEntry {
+    /// The window.
+    pub window: Window,
+    /// The identifier of the window. It is used by [`WindowRegistry::get`]
+    /// and for removing the window on [`Event::Closed`].
+    ///
+    /// # Platform-specific
+    /// - **macOS:** It is `None` without the `macos-private-api` feature.
+    pub id: Option<WindowId>,
+    /// The last known title.
+    pub title: Option<String>,
+    /// The last known bounds in the native coordinate space.
+    pub bounds: Option<Rect>,
+    /// Whether the window is visible. A minimized window is not visible.
+    pub visible: bool,
+    /// When the registry learned about the window.
+    /// It is the time of [`Event::Created`] or of the first event of the window.
+    pub created_at: Instant,
+    /// When the window was focused last time.
+    pub last_focused_at: Option<Instant>,
+}
+
+impl WindowEntry {
+    /// Creates an entry whose properties are not known yet.
+    fn new(window: Window, now: Instant) -> Self {
+        Self {
+            id: window_id(&window),
+            title: None,
+            bounds: None,
+            visible: true,
+            window,
+            created_at: now,
+            last_focused_at: None,
+        }
+    }
+
+    /// Creates an entry with the current properties of the window.
+    ///
+    /// It may block for a while on an application that doesn't respond,
+    /// so it must not be called while the registry is locked.
+    fn query(window: Window, now: Instant) -> Self {
+        Self {
+            title: window.title().ok().flatten(),
+            bounds: window.bounds().ok(),
+            visible: window.is_visible().unwrap_or(true),
+            ..Self::new(window, now)
+        }
+    }
+}
+
+/// Returns the identifier of the window if it is available on the current platform.
+pub(crate) fn window_id(window: &Window) -> Option<WindowId> {
+    #[cfg(target_os = "windows")]
+    {
+        Some(WindowId::new(window.inner().hwnd()))
+    }
+    #[cfg(all(target_os = "macos", feature = "macos-private-api"))]
+    {
+        window.id().ok()
+    }
+    #[cfg(all(target_os = "macos", not(feature = "macos-private-api")))]
+    {
+        let _ = window;
+        None
+    }
+}
+
+/// A change of a [`WindowRegistry`] that is notified to the listeners.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+pub enum RegistryChange {
+    /// A window was added.
+    Added(WindowEntry),
+    /// The bounds, the title or the visibility of a window was changed.
+    Updated(WindowEntry),
+    /// A window was removed.
+    Removed(WindowEntry),
+    /// The focused window was changed.
+    FocusChanged {
+        from: Option<Window>,
+        to: Option<Window>,
+    },
+    /// The stacking order was changed. It has the windows from the front to the back.
+    StackingChanged(Vec<Window>),
+    /// A window became mostly covered or uncovered.
+    /// See [`Event::OcclusionChanged`] for details.
+    OcclusionChanged {
+        window: Window,
+        visible_fraction: f64,
+    },
+}
+
+type Listener = Box<dyn FnMut(&RegistryChange) + Send>;
+
+#[derive(Default)]
+struct State {
+    windows: Vec<WindowEntry>,
+    focused: Option<Window>,
+    /// The windows from the front to the back.
+    stacking: Vec<Window>,
+    /// The windows that are mostly covered by the windows in front of them.
+    covered: Vec<Window>,
+}
+
+impl State {
+    fn index_of(&self, window: &Window) -> Option<usize> {
+        self.windows
+            .iter()
+            .position(|entry| entry.window == *window)
+    }
+
+    /// Returns the entry of the window and adds it if it is not known yet.
+    /// The added entry is taken from `queried` if it is there.
+    fn entry(
+        &mut self,
+        window: &Window,
+        now: Instant,
+        queried: &mut Vec<WindowEntry>,
+        changes: &mut Vec<RegistryChange>,
+    ) -> &mut WindowEntry {
+        let index = self.index_of(window).unwrap_or_else(|| {
+            let entry = match queried.iter().position(|entry| entry.window == *window) {
+                Some(index) => queried.swap_remove(index),
+                None => WindowEntry::new(window.clone(), now),
+            };
+            changes.push(RegistryChange::Added(entry.clone()));
+            self.windows.push(entry);
+            self.windows.len() - 1
+        });
+
+        &mut self.windows[index]
+    }
+
+    fn remove(&mut self, index: usize, changes: &mut Vec<RegistryChange>) {
+        let entry = self.windows.remove(index);
+        self.stacking.retain(|window| *window != entry.window);
+        self.covered.retain(|window| *window != entry.window);
+
+        if self.focused.as_ref() == Some(&entry.window) {
+            changes.push(RegistryChange::FocusChanged {
+                from: self.focused.take(),
+                to: None,
+            });
+        }
+
+        changes.push(RegistryChange::Removed(entry));
+    }
+
+    fn set_focused(&mut self, focused: Option<Window>, changes: &mut Vec<RegistryChange>) {
+        if self.focused != focused {
+            changes.push(RegistryChange::FocusChanged {
+                from: std::mem::replace(&mut self.focused, focused.clone()),
+                to: focused,
+            });
+        }
+    }
+
+    /// Returns the visible windows that have bounds from the front to the back.
+    fn visible_stacking(&self) -> Vec<(&WindowEntry, Rect)> {
+        self.stacking
+            .iter()
+            .filter_map(|window| {
+                let entry = &self.windows[self.index_of(window)?];
+                Some((entry, entry.bounds.filter(|_| entry.visible)?))
+            })
+            .collect()
+    }
+
+    /// Returns the part of the window that is not covered by the windows in front of it.
+    /// A window that is not in the stacking order is regarded as the frontmost one.
+    fn visible_region(&self, window: &Window) -> Option<Region> {
+        let entry = &self.windows[self.index_of(window)?];
+        let bounds = entry.bounds?;
+
+        if !entry.visible {
+            return Some(Region::new());
+        }
+
+        let stacking = self.visible_stacking();
+        let above = stacking
+            .iter()
+            .take_while(|(other, _)| other.window != *window)
+            .map(|(_, rect)| rect);
+        let above: Vec<&Rect> = if stacking.iter().any(|(other, _)| other.window == *window) {
+            above.collect()
+        } else {
+            Vec::new()
+        };
+
+        Some(spatial::visible_region(&bounds, above))
+    }
+
+    fn visible_fraction(&self, window: &Window) -> Option<f64> {
+        let bounds = self.windows[self.index_of(window)?].bounds?;
+        let region = self.visible_region(window)?;
+
+        Some(if bounds.is_empty() {
+            0.
+        } else {
+            (region.area() / bounds.area()).clamp(0., 1.)
+        })
+    }
+
+    /// Records the windows that became mostly covered or uncovered.
+    fn update_occlusion(&mut self, changes: &mut Vec<RegistryChange>) {
+        let fractions: Vec<(Window, f64)> = self
+            .windows
+            .iter()
+            .filter_map(|entry| {
+                let fraction = self.visible_fraction(&entry.window)?;
+                Some((entry.window.clone(), fraction))
+            })
+            .collect();
+
+        for (window, visible_fraction) in fractions {
+            let covered = visible_fraction < MOSTLY_COVERED_THRESHOLD;
+            let index = self.covered.iter().position(|other| *other == window);
+
+            match (covered, index) {
+                (true, None) => self.covered.push(window.clone()),
+                (false, Some(index)) => {
+                    self.covered.swap_remove(index);
+                }
+                _ => continue,
+            }
+
+            changes.push(RegistryChange::OcclusionChanged {
+                window,
+                visible_fraction,
+            });
+        }
+    }
+
+    /// Applies the event. `queried` has the entries of the windows of the event
+    /// that were not known when the event was received.
+    fn apply(
+        &mut self,
+        payload: &MaybeWindowAvailable,
+        mut queried: Vec<WindowEntry>,
+        now: Instant,
+    ) -> Vec<RegistryChange> {
+        let mut changes = self.apply_event(payload, &mut queried, now);
+
+        // The occlusion depends only on the state of the registry.
+        if !changes.is_empty() {
+            self.update_occlusion(&mut changes);
+        }
+
+        changes
+    }
+
+    fn apply_event(
+        &mut self,
+        payload: &MaybeWindowAvailable,
+        queried: &mut Vec<WindowEntry>,
+        now: Instant,
+    ) -> Vec<RegistryChange> {
+        let mut changes = Vec::new();
+
+        let Some(window) = payload.window() else {
+            match payload.event() {
+                Event::Closed { window_id } => {
+                    if let Some(index) = self
+                        .windows
+                        .iter()
+                        .position(|entry| entry.id == Some(*window_id))
+                    {
+                        self.remove(index, &mut changes);
+                    }
+                }
+                Event::StackingChanged { windows } => {
+                    for window in windows {
+                        self.entry(window, now, queried, &mut changes);
+                    }
+
+                    self.stacking = windows.clone();
+                    changes.push(RegistryChange::StackingChanged(windows.clone()));
+                }
+                _ => {}
+            }
+
+            return changes;
+        };
+
+        let is_focused = self.focused.as_ref() == Some(window);
+        let entry = self.entry(window, now, queried, &mut changes);
+        let before = entry.clone();
+
+        match payload.event() {
+            Event::Moved { bounds }
+            | Event::Resized { bounds }
+            | Event::MoveEnded { bounds }
+            | Event::ResizeEnded { bounds } => entry.bounds = Some(*bounds),
+            Event::TitleChanged { new, .. } => entry.title = Some(new.clone()),
+            Event::Hidden | Event::Minimized => entry.visible = false,
+            Event::Showed | Event::Restored => entry.visible = true,
+            Event::Focused => {
+                entry.last_focused_at = Some(now);
+                self.set_focused(Some(window.clone()), &mut changes);
+                return changes;
+            }
+            Event::Unfocused if is_focused => {
+                self.set_focused(None, &mut changes);
+                return changes;
+            }
+            _ => {}
+        }
+
+        // The added entry already has the current state.
+        if *entry != before && !matches!(changes.first(), Some(RegistryChange::Added(_))) {
+            changes.push(RegistryChange::Updated(entry.clone()));
+        }
+
+        changes
+    }
+}
+
+struct Shared {
+    state: RwLock<State>,
+    listeners: Mutex<Vec<Listener>>,
+}
+
+/// A live model of the observed windows, kept up to date by the events of the observer.
+///
+/// It is cheap to clone and the clones share the same model, so it can be shared across tasks.
+/// The events are applied in the order of the event stream before they are sent to the channel,
+/// so the registry already reflects an event when the consumer receives it.
+///
+/// The windows are added by [`Event::Created`] or by their first event,
+/// so the windows that existed before the observer started are known
+/// after their first event or after the initial snapshot.
+/// See [`WindowObserverBuilder::initial_snapshot`](crate::WindowObserverBuilder::initial_snapshot).
+/// They are removed by [`Event::Closed`].
+///
+/// # Platform-specific
+/// - **macOS:** [`Event::Closed`] requires the `macos-private-api` feature,
+///   so the closed windows are not removed without it.
+#[derive(Clone)]
+pub struct WindowRegistry {
+    shared: Arc<Shared>,
+}
+
+impl Default for WindowRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WindowRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                state: RwLock::new(State::default()),
+                listeners: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, State> {
+        self.shared.state.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, State> {
+        self.shared.state.write().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Returns the known windows in the order they were added.
+    pub fn windows(&self) -> Vec<WindowEntry> {
+        self.read().windows.clone()
+    }
+
+    /// Returns the focused window if it is known.
+    pub fn focused(&self) -> Option<WindowEntry> {
+        let state = self.read();
+        let focused = state.focused.as_ref()?;
+
+        state
+            .index_of(focused)
+            .map(|index| state.windows[index].clone())
+    }
+
+    /// Returns the windows from the front to the back as of the last [`Event::StackingChanged`].
+    pub fn stacking(&self) -> Vec<WindowEntry> {
+        let state = self.read();
+
+        state
+            .stacking
+            .iter()
+            .filter_map(|window| state.index_of(window))
+            .map(|index| state.windows[index].clone())
+            .collect()
+    }
+
+    /// Returns the frontmost visible window that contains the point.
+    /// It only considers the windows in the stacking order. See [`stacking`](Self::stacking).
+    pub fn window_at(&self, point: Position) -> Option<WindowEntry> {
+        let state = self.read();
+        let stacking = state.visible_stacking();
+        let index = spatial::index_at(point, stacking.iter().map(|(_, rect)| rect))?;
+
+        Some(stacking[index].0.clone())
+    }
+
+    /// Returns the part of the window that is not covered by the windows in front of it.
+    ///
+    /// Only the windows of the registry are regarded as covering the window,
+    /// and the order is the one of the last [`Event::StackingChanged`].
+    /// A window that is not in the order is regarded as the frontmost one.
+    /// It returns `None` if the window or its bounds are not known.
+    pub fn visible_region(&self, window: &Window) -> Option<Region> {
+        self.read().visible_region(window)
+    }
+
+    /// Returns the fraction of the area of the window that is not covered
+    /// by the windows in front of it. See [`visible_region`](Self::visible_region) for details.
+    pub fn visible_fraction(&self, window: &Window) -> Option<f64> {
+        self.read().visible_fraction(window)
+    }
+
+    /// Returns the window that has the given identifier.
+    pub fn get(&self, id: WindowId) -> Option<WindowEntry> {
+        self.read()
+            .windows
+            .iter()
+            .find(|entry| entry.id == Some(id))
+            .cloned()
+    }
+
+    /// Returns the entry of the given window.
+    pub fn get_by_window(&self, window: &Window) -> Option<WindowEntry> {
+        let state = self.read();
+        state
+            .index_of(window)
+            .map(|index| state.windows[index].clone())
+    }
+
+    /// Returns the number of known windows.
+    pub fn len(&self) -> usize {
+        self.read().windows.len()
+    }
+
+    /// Returns `true` if no window is known.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Calls the listener for each change of the registry.
+    ///
+    /// The listener is called after the change is applied, on the thread that sends the events,
+    /// so it should return quickly. It can query the registry.
+    pub fn subscribe(&self, listener: impl FnMut(&RegistryChange) + Send + 'static) {
+        self.shared
+            .listeners
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(Box::new(listener));
+    }
+
+    /// Applies an event to the registry and returns the changes. The errors are ignored.
+    ///
+    /// It is called by [`RegistrySink`], so it is only needed for feeding the registry manually.
+    pub fn apply(&self, result: &EventResult) -> Vec<RegistryChange> {
+        let Ok(payload) = result else {
+            return Vec::new();
+        };
+
+        let now = Instant::now();
+        let queried = self.query_new_windows(payload, now);
+        let changes = self.write().apply(payload, queried, now);
+
+        if !changes.is_empty() {
+            let mut listeners = self
+                .shared
+                .listeners
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+
+            for change in &changes {
+                for listener in listeners.iter_mut() {
+                    listener(change);
+                }
+            }
+        }
+
+        changes
+    }
+
+    /// Returns the entries of the windows of the event that are not known yet.
+    /// They are queried before the registry is locked so that the readers are not blocked.
+    fn query_new_windows(&self, payload: &MaybeWindowAvailable, now: Instant) -> Vec<WindowEntry> {
+        let windows: Vec<Window> = {
+            let state = self.read();
+            let windows = match payload.event() {
+                Event::StackingChanged { windows } => windows.as_slice(),
+                _ => payload
+                    .window()
+                    .map(std::slice::from_ref)
+                    .unwrap_or_default(),
+            };
+
+            windows
+                .iter()
+                .filter(|window| state.index_of(window).is_none())
+                .cloned()
+                .collect()
+        };
+
+        windows
+            .into_iter()
+            .map(|window| WindowEntry::query(window, now))
+            .collect()
+    }
+
+    /// Creates a sink that applies the events to the registry before sending them to the given sink.
+    pub fn sink(&self, event_tx: impl Into<EventSender>) -> RegistrySink {
+        RegistrySink {
+            registry: self.clone(),
+            event_tx: event_tx.into(),
+            occlusion_events: false,
+        }
+    }
+}
+
+impl std::fmt::Debug for WindowRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.read();
+
+        f.debug_struct("WindowRegistry")
+            .field("windows", &state.windows)
+            .field("focused", &state.focused)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An [`EventSink`] that applies the events to a [`WindowRegistry`]
+/// before sending them to another sink. It is created by [`WindowRegistry::sink`].
+#[derive(Debug)]
+pub struct RegistrySink {
+    registry: WindowRegistry,
+    event_tx: EventSender,
+    occlusion_events: bool,
+}
+
+impl RegistrySink {
+    /// Sends [`Event::OcclusionChanged`] after the event that changed the occlusion.
+    /// It is disabled by default.
+    pub fn occlusion_events(mut self, enabled: bool) -> Self {
+        self.occlusion_events = enabled;
+        self
+    }
+}
+
+impl EventSink for RegistrySink {
+    fn send(&mut self, result: EventResult) -> bool {
+        let changes = self.registry.apply(&result);
+
+        if !self.event_tx.send(result) {
+            return false;
+        }
+
+        if self.occlusion_events {
+            for change in changes {
+                if let RegistryChange::OcclusionChanged {
+                    window,
+                    visible_fraction,
+                } = change
+                {
+                    let event = Event::OcclusionChanged { visible_fraction };
+
+                    if !self
+                        .event_tx
+                        .send(Ok(MaybeWindowAvailable::Available { window, event }))
+                    {
+                        return false;
+                    }
+                }
+            }
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::window::test_window;
+
+    fn entry(window: &Window, bounds: Rect, now: Instant) -> WindowEntry {
+        WindowEntry {
+            window: window.clone(),
+            id: None,
+            title: None,
+            bounds: Some(bounds),
+            visible: true,
+            created_at: now,
+            last_focused_at: None,
+        }
+    }
+
+    fn moved(window: &Window, bounds: Rect) -> MaybeWindowAvailable {
+        MaybeWindowAvailable::Available {
+            window: window.clone(),
+            event: Event::Moved { bounds },
+        }
+    }
+
+    fn occlusion_changes(changes: &[RegistryChange]) -> Vec<(&Window, f64)> {
+        changes
+            .iter()
+            .filter_map(|change| match change {
+                RegistryChange::OcclusionChanged {
+                    window,
+                    visible_fraction,
+                } => Some((window, *visible_fraction)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn rect(x: f64, width: f64) -> Rect {
+        Rect {
+            x,
+            y: 0.,
+            width,
+            height: 100.,
+        }
+    }
+
+    #[test]
+    fn occlusion_changes_when_the_threshold_is_crossed() {
+        let now = Instant::now();
+        let (front, back) = (test_window(1), test_window(2));
+        let mut state = State {
+            windows: vec![
+                entry(&front, rect(0., 40.), now),
+                entry(&back, rect(0., 100.), now),
+            ],
+            stacking: vec![front.clone(), back.clone()],
+            ..Default::default()
+        };
+
+        // 60% of the back window is visible, which is not mostly covered.
+        state.update_occlusion(&mut Vec::new());
+        assert_eq!(state.visible_fraction(&back), Some(0.6));
+        assert!(state.covered.is_empty());
+
+        let changes = state.apply(&moved(&front, rect(0., 60.)), Vec::new(), now);
+        assert_eq!(occlusion_changes(&changes), [(&back, 0.4)]);
+
+        // It stays mostly covered, so nothing is notified.
+        let changes = state.apply(&moved(&front, rect(0., 70.)), Vec::new(), now);
+        assert!(occlusion_changes(&changes).is_empty());
+
+        let changes = state.apply(&moved(&front, rect(50., 40.)), Vec::new(), now);
+        assert_eq!(occlusion_changes(&changes), [(&back, 0.6)]);
+    }
+
+    #[test]
+    fn moved_applies_the_bounds_of_the_event() {
+        let now = Instant::now();
+        let window = test_window(1);
+        let mut state = State {
+            windows: vec![entry(&window, rect(0., 100.), now)],
+            ..Default::default()
+        };
+
+        let changes = state.apply(&moved(&window, rect(10., 100.)), Vec::new(), now);
+
+        assert_eq!(state.windows[0].bounds, Some(rect(10., 100.)));
+        assert!(matches!(
+            changes.as_slice(),
+            [RegistryChange::Updated(entry)] if entry.bounds == Some(rect(10., 100.))
+        ));
+    }
+
+    fn available(window: &Window, event: Event) -> EventResult {
+        Ok(MaybeWindowAvailable::Available {
+            window: window.clone(),
+            event,
+        })
+    }
+
+    /// Creates a registry that already knows the windows, so that they are not queried.
+    fn registry_with(windows: &[&Window]) -> WindowRegistry {
+        let registry = WindowRegistry::new();
+        registry.write().windows = windows
+            .iter()
+            .map(|window| entry(window, rect(0., 100.), Instant::now()))
+            .collect();
+        registry
+    }
+
+    #[test]
+    fn focused_follows_the_focus_events() {
+        let (a, b) = (test_window(1), test_window(2));
+        let registry = registry_with(&[&a, &b]);
+
+        registry.apply(&available(&a, Event::Focused));
+        let focused = registry.focused().unwrap();
+        assert_eq!(focused.window, a);
+        let focused_at = focused.last_focused_at.unwrap();
+
+        registry.apply(&available(&b, Event::Focused));
+        assert_eq!(registry.focused().unwrap().window, b);
+
+        // The window that is not focused anymore doesn't unfocus the focused one.
+        registry.apply(&available(&a, Event::Unfocused));
+        assert_eq!(registry.focused().unwrap().window, b);
+
+        registry.apply(&available(&b, Event::Unfocused));
+        assert!(registry.focused().is_none());
+
+        registry.apply(&available(&a, Event::Focused));
+        assert!(registry.get_by_window(&a).unwrap().last_focused_at.unwrap() >= focused_at);
+    }
+
+    #[test]
+    fn closed_removes_the_window() {
+        let (a, b) = (test_window(1), test_window(2));
+        let registry = registry_with(&[&a, &b]);
+        let id = WindowId::from(7);
+        registry.write().windows[0].id = Some(id);
+        registry.apply(&available(&a, Event::Focused));
+
+        let changes = registry.apply(&Ok(MaybeWindowAvailable::NotAvailable {
+            event: Event::Closed { window_id: id },
+        }));
+
+        assert!(matches!(
+            changes.as_slice(),
+            [
+                RegistryChange::FocusChanged { to: None, .. },
+                RegistryChange::Removed(entry),
+            ] if entry.window == a
+        ));
+        assert_eq!(registry.len(), 1);
+        assert!(registry.get(id).is_none());
+        assert!(registry.focused().is_none());
+    }
+
+    #[test]
+    fn listeners_receive_the_changes() {
+        let window = test_window(1);
+        let registry = registry_with(&[&window]);
+        let received = Arc::new(Mutex::new(Vec::new()));
+        registry.subscribe({
+            let received = Arc::clone(&received);
+            move |change| received.lock().unwrap().push(change.clone())
+        });
+
+        let title_changed = Event::TitleChanged {
+            old: None,
+            new: "title".to_owned(),
+        };
+        let changes = registry.apply(&available(&window, title_changed.clone()));
+        // The same event doesn't change the registry again.
+        registry.apply(&available(&window, title_changed));
+
+        let received = received.lock().unwrap();
+        assert_eq!(*received, changes);
+        assert!(matches!(
+            received.as_slice(),
+            [RegistryChange::Updated(entry)] if entry.title.as_deref() == Some("title")
+        ));
+    }
+
+    #[test]
+    fn a_new_window_takes_the_queried_entry() {
+        let now = Instant::now();
+        let window = test_window(1);
+        let mut state = State::default();
+        let queried = WindowEntry {
+            title: Some("queried".to_owned()),
+            ..entry(&window, rect(0., 100.), now)
+        };
+
+        let changes = state.apply(&moved(&window, rect(10., 100.)), vec![queried], now);
+
+        assert!(matches!(
+            changes.as_slice(),
+            [RegistryChange::Added(entry)] if entry.title.as_deref() == Some("queried")
+        ));
+        assert_eq!(state.windows[0].bounds, Some(rect(10., 100.)));
+    }
+}
@@ -0,0 +1,164 @@
+//! Destinations of the events sent by the observer.
+//!
+//! The interpreters of the observer deliver events to an [`EventSink`].
+//! It is implemented for the unbounded channel of tokio with the `tokio` feature,
+//! the channels of [`channel`](crate::channel), the channels of `std::sync::mpsc`,
+//! the channels of `crossbeam-channel` with the `crossbeam` feature,
+//! and closures that take an [`EventResult`].
+
+use std::{
+    any::Any,
+    panic::{AssertUnwindSafe, catch_unwind},
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    EventFilter, EventResult,
+    channel::{BoundedEventTx, DiscardCounter, DiscardedEvents},
+};
+
+/// A destination of the events sent by the observer.
+///
+/// # Notes
+/// The events are sent from the thread that receives them from the OS.
+/// It is the thread of `CFRunLoop` on macOS and the thread of the hook on Windows.
+/// So the sink should return quickly.
+pub trait EventSink: Send + 'static {
+    /// Receives an event from the observer.
+    ///
+    /// It returns `false` if the sink is closed, for example when the receiver has been dropped.
+    fn send(&mut self, result: EventResult) -> bool;
+}
+
+impl<F> EventSink for F
+where
+    F: FnMut(EventResult) + Send + 'static,
+{
+    fn send(&mut self, result: EventResult) -> bool {
+        self(result);
+        true
+    }
+}
+
+#[cfg(feature = "tokio")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+impl EventSink for tokio::sync::mpsc::UnboundedSender<EventResult> {
+    fn send(&mut self, result: EventResult) -> bool {
+        tokio::sync::mpsc::UnboundedSender::send(self, result).is_ok()
+    }
+}
+
+impl EventSink for BoundedEventTx {
+    fn send(&mut self, result: EventResult) -> bool {
+        BoundedEventTx::send(self, result).is_ok()
+    }
+}
+
+impl EventSink for std::sync::mpsc::Sender<EventResult> {
+    fn send(&mut self, result: EventResult) -> bool {
+        std::sync::mpsc::Sender::send(self, result).is_ok()
+    }
+}
+
+impl EventSink for std::sync::mpsc::SyncSender<EventResult> {
+    fn send(&mut self, result: EventResult) -> bool {
+        std::sync::mpsc::SyncSender::send(self, result).is_ok()
+    }
+}
+
+#[cfg(feature = "crossbeam")]
+#[cfg_attr(docsrs, doc(cfg(feature = "crossbeam")))]
+impl EventSink for crossbeam_channel::Sender<EventResult> {
+    fn send(&mut self, result: EventResult) -> bool {
+        crossbeam_channel::Sender::send(self, result).is_ok()
+    }
+}
+
+/// An [`EventSink`] that drops the events that are observed only for the stages before it,
+/// such as the events that a [`WindowRegistry`](crate::WindowRegistry) needs for the occlusion.
+pub(crate) struct FilteringSink {
+    dropped: EventFilter,
+    event_tx: EventSender,
+}
+
+impl FilteringSink {
+    pub fn new(dropped: EventFilter, event_tx: EventSender) -> Self {
+        Self { dropped, event_tx }
+    }
+}
+
+impl EventSink for FilteringSink {
+    fn send(&mut self, result: EventResult) -> bool {
+        if let Ok(payload) = &result
+            && self.dropped.should_dispatch(payload.event())
+        {
+            return true;
+        }
+
+        self.event_tx.send(result)
+    }
+}
+
+/// A shared handle of an [`EventSink`] that the observer sends events through.
+///
+/// Any [`EventSink`] can be converted into it by [`From`],
+/// so a channel or a closure can be passed to [`WindowObserver::start`](crate::WindowObserver::start).
+#[derive(Clone)]
+pub struct EventSender {
+    sink: Arc<Mutex<dyn EventSink>>,
+    /// The counts of the channel of [`channel`](crate::channel) that the events end up in.
+    discarded: Option<DiscardCounter>,
+}
+
+impl EventSender {
+    /// Creates a new `EventSender` from an [`EventSink`].
+    pub fn new(sink: impl EventSink) -> Self {
+        let discarded = (&sink as &dyn Any)
+            .downcast_ref::<BoundedEventTx>()
+            .map(BoundedEventTx::discard_counter);
+
+        Self {
+            sink: Arc::new(Mutex::new(sink)),
+            discarded,
+        }
+    }
+
+    /// Returns the number of events discarded by the channel that the events end up in,
+    /// or `None` if it is not a channel of [`channel`](crate::channel).
+    pub fn discarded(&self) -> Option<DiscardedEvents> {
+        self.discarded.as_ref().map(DiscardCounter::get)
+    }
+
+    pub(crate) fn discard_counter(&self) -> Option<DiscardCounter> {
+        self.discarded.clone()
+    }
+
+    /// Keeps the counts of the channel behind a sink that wraps this sender's one.
+    pub(crate) fn with_discard_counter(mut self, discarded: Option<DiscardCounter>) -> Self {
+        self.discarded = discarded;
+        self
+    }
+
+    /// Sends an event to the sink.
+    ///
+    /// A panic in the sink is caught so that it doesn't unwind into the thread of the OS.
+    /// In that case, the event is lost and `true` is returned because the sink is not closed.
+    /// It returns `false` if the sink is closed.
+    pub fn send(&self, result: EventResult) -> bool {
+        let mut sink = self.sink.lock().unwrap_or_else(|e| e.into_inner());
+
+        catch_unwind(AssertUnwindSafe(|| sink.send(result))).unwrap_or(true)
+    }
+}
+
+impl<S: EventSink> From<S> for EventSender {
+    fn from(sink: S) -> Self {
+        Self::new(sink)
+    }
+}
+
+impl std::fmt::Debug for EventSender {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("EventSender").finish_non_exhaustive()
+    }
+}
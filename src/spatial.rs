@@ -0,0 +1,244 @@
+//! Occlusion and hit-testing on top of the geometry and the stacking order of windows.
+//!
+//! The computation is pure. It takes the bounds of the windows from the front to the back,
+//! such as [`WindowRegistry::stacking`](crate::WindowRegistry::stacking), and doesn't query the OS.
+//! [`WindowRegistry`](crate::WindowRegistry) provides the queries for the observed windows.
+
+use crate::{Position, Rect};
+
+/// The fraction of the area below which a window is regarded as mostly covered.
+pub const MOSTLY_COVERED_THRESHOLD: f64 = 0.5;
+
+/// An area made of rectangles that don't overlap each other.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Region {
+    rects: Vec<Rect>,
+}
+
+impl Region {
+    /// Creates an empty region.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a region that covers the rectangle.
+    pub fn from_rect(rect: Rect) -> Self {
+        Self {
+            rects: if rect.is_empty() {
+                Vec::new()
+            } else {
+                vec![rect]
+            },
+        }
+    }
+
+    /// Returns the rectangles of the region. They don't overlap each other.
+    pub fn rects(&self) -> &[Rect] {
+        &self.rects
+    }
+
+    /// Returns the area of the region.
+    pub fn area(&self) -> f64 {
+        self.rects.iter().map(Rect::area).sum()
+    }
+
+    /// Returns `true` if the region has no area.
+    pub fn is_empty(&self) -> bool {
+        self.rects.is_empty()
+    }
+
+    /// Checks if the point is inside the region.
+    pub fn contains(&self, point: Position) -> bool {
+        self.rects.iter().any(|rect| rect.contains(point))
+    }
+
+    /// Returns the smallest rectangle that contains the region, or `None` if it is empty.
+    pub fn bounds(&self) -> Option<Rect> {
+        self.rects.iter().copied().reduce(|a, b| a.union(&b))
+    }
+
+    /// Removes the area covered by the rectangle from the region.
+    pub fn subtract(&mut self, rect: &Rect) {
+        self.rects = self
+            .rects
+            .iter()
+            .flat_map(|part| part.subtract(rect))
+            .collect();
+    }
+}
+
+/// Returns the part of `bounds` that is not covered by `above`,
+/// the bounds of the windows in front of the window.
+pub fn visible_region<'a>(bounds: &Rect, above: impl IntoIterator<Item = &'a Rect>) -> Region {
+    let mut region = Region::from_rect(*bounds);
+
+    for rect in above {
+        if region.is_empty() {
+            break;
+        }
+
+        region.subtract(rect);
+    }
+
+    region
+}
+
+/// Returns the fraction of the area of `bounds` that is not covered by `above`.
+/// It is `0.0` if `bounds` has no area.
+pub fn visible_fraction<'a>(bounds: &Rect, above: impl IntoIterator<Item = &'a Rect>) -> f64 {
+    if bounds.is_empty() {
+        return 0.;
+    }
+
+    (visible_region(bounds, above).area() / bounds.area()).clamp(0., 1.)
+}
+
+/// Returns the index of the frontmost rectangle that contains the point.
+/// The rectangles must be ordered from the front to the back.
+pub fn index_at<'a>(
+    point: Position,
+    stacking: impl IntoIterator<Item = &'a Rect>,
+) -> Option<usize> {
+    stacking.into_iter().position(|rect| rect.contains(point))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: f64, y: f64, width: f64, height: f64) -> Rect {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    fn point(x: f64, y: f64) -> Position {
+        Position { x, y }
+    }
+
+    fn total_area(rects: &[Rect]) -> f64 {
+        rects.iter().map(Rect::area).sum()
+    }
+
+    fn assert_disjoint(rects: &[Rect]) {
+        for (i, a) in rects.iter().enumerate() {
+            for b in &rects[i + 1..] {
+                assert!(!a.intersects(b), "{a:?} overlaps {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn rect_subtract_without_overlap_keeps_the_rect() {
+        let a = rect(0., 0., 10., 10.);
+
+        assert_eq!(a.subtract(&rect(20., 20., 5., 5.)), [a]);
+        // Touching edges don't overlap.
+        assert_eq!(a.subtract(&rect(10., 0., 5., 10.)), [a]);
+        assert!(
+            rect(0., 0., 0., 10.)
+                .subtract(&rect(20., 0., 5., 5.))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn rect_subtract_covering_rect_leaves_nothing() {
+        let a = rect(0., 0., 10., 10.);
+
+        assert!(a.subtract(&a).is_empty());
+        assert!(a.subtract(&rect(-5., -5., 20., 20.)).is_empty());
+    }
+
+    #[test]
+    fn rect_subtract_inner_rect_leaves_four_bands() {
+        let a = rect(0., 0., 10., 10.);
+        let parts = a.subtract(&rect(2., 3., 4., 5.));
+
+        assert_eq!(
+            parts,
+            [
+                rect(0., 0., 10., 3.),
+                rect(0., 8., 10., 2.),
+                rect(0., 3., 2., 5.),
+                rect(6., 3., 4., 5.),
+            ]
+        );
+        assert_disjoint(&parts);
+        assert_eq!(total_area(&parts), 100. - 20.);
+    }
+
+    #[test]
+    fn rect_subtract_corner_leaves_two_parts() {
+        let a = rect(0., 0., 10., 10.);
+        let parts = a.subtract(&rect(5., 5., 10., 10.));
+
+        assert_eq!(parts, [rect(0., 0., 10., 5.), rect(0., 5., 5., 5.)]);
+        assert_eq!(total_area(&parts), 75.);
+    }
+
+    #[test]
+    fn region_subtract_keeps_the_parts_disjoint() {
+        let mut region = Region::from_rect(rect(0., 0., 10., 10.));
+        region.subtract(&rect(0., 0., 5., 5.));
+        region.subtract(&rect(5., 5., 5., 5.));
+
+        assert_disjoint(region.rects());
+        assert_eq!(region.area(), 50.);
+        assert!(region.contains(point(7., 2.)));
+        assert!(region.contains(point(2., 7.)));
+        assert!(!region.contains(point(2., 2.)));
+        assert!(!region.contains(point(7., 7.)));
+        assert_eq!(region.bounds(), Some(rect(0., 0., 10., 10.)));
+
+        region.subtract(&rect(0., 0., 10., 10.));
+        assert!(region.is_empty());
+        assert_eq!(region.bounds(), None);
+    }
+
+    #[test]
+    fn region_from_empty_rect_is_empty() {
+        assert!(Region::from_rect(rect(0., 0., 0., 10.)).is_empty());
+        assert_eq!(Region::new().area(), 0.);
+    }
+
+    #[test]
+    fn visible_fraction_of_overlapping_windows() {
+        let bounds = rect(0., 0., 100., 100.);
+
+        assert_eq!(visible_fraction(&bounds, []), 1.);
+        assert_eq!(visible_fraction(&bounds, &[rect(0., 0., 50., 100.)]), 0.5);
+        // The overlap of the windows above is not subtracted twice.
+        assert_eq!(
+            visible_fraction(
+                &bounds,
+                &[rect(0., 0., 50., 100.), rect(25., 0., 50., 100.)]
+            ),
+            0.25
+        );
+        assert_eq!(
+            visible_fraction(&bounds, &[rect(-10., -10., 200., 200.)]),
+            0.
+        );
+        assert_eq!(visible_fraction(&rect(0., 0., 0., 0.), []), 0.);
+    }
+
+    #[test]
+    fn index_at_returns_the_frontmost_window() {
+        let stacking = [
+            rect(0., 0., 10., 10.),
+            rect(0., 0., 100., 100.),
+            rect(50., 50., 100., 100.),
+        ];
+
+        assert_eq!(index_at(point(5., 5.), &stacking), Some(0));
+        assert_eq!(index_at(point(60., 60.), &stacking), Some(1));
+        assert_eq!(index_at(point(120., 120.), &stacking), Some(2));
+        assert_eq!(index_at(point(-1., 0.), &stacking), None);
+        // The right and bottom edges are outside.
+        assert_eq!(index_at(point(10., 5.), &stacking[..1]), None);
+    }
+}
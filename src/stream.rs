@@ -0,0 +1,125 @@
+//! An event stream that owns its observer.
+
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_core::Stream;
+
+use crate::{
+    Error, Event, EventFilter, EventResult, MaybeWindowAvailable, Window, WindowObserver,
+    channel::{BoundedEventRx, DiscardedEvents},
+};
+
+type EventPredicate = Box<dyn Fn(&EventResult) -> bool + Send + Sync>;
+
+/// A stream of the events of a [`WindowObserver`] that owns the observer.
+///
+/// It is created by [`WindowObserver::start_stream`] or
+/// [`WindowObserverBuilder::start_stream`](crate::WindowObserverBuilder::start_stream).
+/// Dropping the stream stops the observer.
+///
+/// It implements [`Stream`], so it can be used with the combinators of the `futures` crate
+/// or with `tokio-stream`. It doesn't depend on a specific async runtime.
+pub struct EventStream {
+    event_rx: BoundedEventRx,
+    predicates: Vec<EventPredicate>,
+    observer: WindowObserver,
+}
+
+impl EventStream {
+    pub(crate) fn new(event_rx: BoundedEventRx, observer: WindowObserver) -> Self {
+        Self {
+            event_rx,
+            predicates: Vec::new(),
+            observer,
+        }
+    }
+
+    /// Returns the observer that sends the events.
+    pub fn observer(&self) -> &WindowObserver {
+        &self.observer
+    }
+
+    /// Returns the number of events discarded by the channel of the stream.
+    /// The events dropped by [`filter`](Self::filter) are not counted.
+    pub fn discarded(&self) -> DiscardedEvents {
+        self.event_rx.discarded()
+    }
+
+    /// Stops the observer and drops the stream.
+    pub async fn stop(self) -> Result<(), Error> {
+        self.observer.stop().await
+    }
+
+    /// Keeps only the events that the predicate returns `true` for.
+    pub fn filter(
+        mut self,
+        predicate: impl Fn(&EventResult) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.predicates.push(Box::new(predicate));
+        self
+    }
+
+    /// Keeps only the events of the given window. Errors are kept.
+    ///
+    /// # Platform-specific
+    /// - **macOS:** [`Event::Closed`] doesn't have the window, so it is kept only when the ID
+    ///   of the window is available. It requires the `macos-private-api` feature.
+    pub fn for_window(self, window: Window) -> Self {
+        let window_id = crate::registry::window_id(&window);
+
+        self.filter(move |result| match result {
+            Ok(MaybeWindowAvailable::NotAvailable {
+                event: Event::Closed { window_id: closed },
+            }) => Some(*closed) == window_id,
+            // The marker of the initial state snapshot is kept for every window.
+            Ok(MaybeWindowAvailable::NotAvailable {
+                event: Event::InitialStateCompleted,
+            }) => true,
+            Ok(maybe_window) => maybe_window.window() == Some(&window),
+            Err(_) => true,
+        })
+    }
+
+    /// Keeps only the kinds of events enabled in the given filter.
+    /// Errors and [`Event::InitialStateCompleted`] are kept.
+    pub fn of_kind(self, event_filter: EventFilter) -> Self {
+        self.filter(move |result| match result {
+            Ok(maybe_window) => {
+                let event = maybe_window.event();
+                *event == Event::InitialStateCompleted || event_filter.should_dispatch(event)
+            }
+            Err(_) => true,
+        })
+    }
+}
+
+impl Stream for EventStream {
+    type Item = EventResult;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            match this.event_rx.poll_recv(cx) {
+                Poll::Ready(Some(result))
+                    if !this.predicates.iter().all(|predicate| predicate(&result)) =>
+                {
+                    continue;
+                }
+                poll => return poll,
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for EventStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventStream")
+            .field("event_rx", &self.event_rx)
+            .field("predicates", &self.predicates.len())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,478 @@
+//! Observation of applications that may not be running yet or that restart.
+
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    sync::{
+        Arc, Mutex,
+        mpsc::{self, RecvTimeoutError},
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    Error, Event, EventFilter, EventResult, EventSender, EventSink, MaybeWindowAvailable,
+    ObserverEvent, WindowObserver,
+    executor::block_on,
+    platform_impl::{is_process_running, process_path},
+};
+
+/// How often the supervisor looks for a matching process and checks whether it is still alive.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How long a [`SupervisedWindowObserver`] waits before attaching again
+/// after attaching has failed or the backend has ended by itself.
+///
+/// The delay starts at `initial` and is multiplied by `multiplier` on each consecutive failure
+/// up to `max`. The failures are forgotten once an observer has kept running for `max`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Backoff {
+    /// The delay after the first failure.
+    pub initial: Duration,
+    /// The longest delay.
+    pub max: Duration,
+    /// The factor by which the delay grows on each failure.
+    pub multiplier: f64,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial: POLL_INTERVAL,
+            max: Duration::from_secs(30),
+            multiplier: 2.,
+        }
+    }
+}
+
+impl Backoff {
+    /// Returns the delay after the given number of consecutive failures before the current one.
+    pub fn delay(&self, failures: u32) -> Duration {
+        let factor = self
+            .multiplier
+            .max(1.)
+            .powi(failures.min(i32::MAX as u32) as i32);
+
+        Duration::try_from_secs_f64(self.initial.as_secs_f64() * factor)
+            .map_or(self.max, |delay| delay.min(self.max))
+    }
+}
+
+/// A predicate that receives the process ID and the process name.
+pub type ProcessPredicate = Arc<dyn Fn(u32, &str) -> bool + Send + Sync>;
+
+/// Describes which process a [`SupervisedWindowObserver`] should attach to.
+///
+/// Processes are discovered through their windows,
+/// so a process is found only after it has opened at least one window.
+#[derive(Clone)]
+pub enum ProcessMatcher {
+    /// Matches processes by name. The comparison ignores ASCII case.
+    ///
+    /// # Platform-specific
+    /// - **Windows:** The name is the file name of the executable such as `notepad.exe`.
+    /// - **macOS:** The name is the name of the application such as `TextEdit`.
+    Name(String),
+    /// Matches processes by the absolute path of their executable.
+    ///
+    /// # Platform-specific
+    /// - **Windows:** The comparison ignores ASCII case.
+    /// - **macOS:** The path of an application is the executable in its bundle,
+    ///   such as `/System/Applications/TextEdit.app/Contents/MacOS/TextEdit`.
+    Path(PathBuf),
+    /// Matches processes for which the predicate returns `true`.
+    /// The predicate receives the process ID and the process name.
+    Predicate(ProcessPredicate),
+}
+
+impl ProcessMatcher {
+    /// Creates a [`ProcessMatcher::Name`].
+    pub fn name(name: impl Into<String>) -> Self {
+        Self::Name(name.into())
+    }
+
+    /// Creates a [`ProcessMatcher::Path`].
+    pub fn path(path: impl Into<PathBuf>) -> Self {
+        Self::Path(path.into())
+    }
+
+    /// Creates a [`ProcessMatcher::Predicate`].
+    pub fn predicate(predicate: impl Fn(u32, &str) -> bool + Send + Sync + 'static) -> Self {
+        Self::Predicate(Arc::new(predicate))
+    }
+
+    /// Checks whether the process matches.
+    pub fn matches(&self, pid: u32, name: &str) -> bool {
+        match self {
+            Self::Name(expected) => expected.eq_ignore_ascii_case(name),
+            Self::Path(expected) => {
+                process_path(pid as _).is_some_and(|path| is_same_path(&path, expected))
+            }
+            Self::Predicate(predicate) => predicate(pid, name),
+        }
+    }
+
+    /// Finds the ID of a running process that matches.
+    pub fn find(&self) -> Option<u32> {
+        let windows = window_getter::get_windows().ok()?;
+
+        windows.iter().find_map(|window| {
+            let pid = window.owner_pid().ok()? as u32;
+            let name = window.owner_name().ok().flatten()?;
+
+            self.matches(pid, &name).then_some(pid)
+        })
+    }
+}
+
+/// Compares the paths of executables, ignoring ASCII case on Windows
+/// whose file systems are case-insensitive.
+fn is_same_path(path: &Path, expected: &Path) -> bool {
+    #[cfg(target_os = "windows")]
+    {
+        path.as_os_str().eq_ignore_ascii_case(expected.as_os_str())
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        path == expected
+    }
+}
+
+impl fmt::Debug for ProcessMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Name(name) => f.debug_tuple("Name").field(name).finish(),
+            Self::Path(path) => f.debug_tuple("Path").field(path).finish(),
+            Self::Predicate(_) => f.debug_tuple("Predicate").finish_non_exhaustive(),
+        }
+    }
+}
+
+/// Observes window events of whichever process matches a [`ProcessMatcher`].
+///
+/// It waits for a matching process to appear, attaches a [`WindowObserver`] to it,
+/// and attaches again to the next matching process after the current one exits.
+/// [`Event::ProcessAttached`] and [`Event::ProcessDetached`] are sent on the same channel
+/// as the window events.
+///
+/// When the backend of the observer ends by itself (see [`WindowObserver::is_running`]),
+/// it is restarted after the delay of its [`Backoff`], and [`ObserverEvent::Recovered`]
+/// is sent once it has restarted.
+pub struct SupervisedWindowObserver {
+    stop_tx: mpsc::Sender<()>,
+    handle: JoinHandle<()>,
+}
+
+impl SupervisedWindowObserver {
+    /// Starts supervising processes that match the given matcher.
+    ///
+    /// # Platform-specific
+    /// - **macOS:** It returns [`Error::PermissionDenied`] when the accessibility permission is
+    ///   not granted. Other errors of attaching are retried until the application is ready.
+    pub fn start(
+        matcher: ProcessMatcher,
+        event_tx: impl Into<EventSender>,
+        event_filter: EventFilter,
+    ) -> Result<Self, Error> {
+        Self::start_with_backoff(matcher, event_tx, event_filter, Backoff::default())
+    }
+
+    /// Same as [`start`](Self::start), but with the given delays of attaching again.
+    pub fn start_with_backoff(
+        matcher: ProcessMatcher,
+        event_tx: impl Into<EventSender>,
+        event_filter: EventFilter,
+        backoff: Backoff,
+    ) -> Result<Self, Error> {
+        #[cfg(target_os = "macos")]
+        if !crate::platform_impl::macos::binding_ax_function::ax_is_process_trusted() {
+            return Err(Error::PermissionDenied);
+        }
+
+        let event_tx = event_tx.into();
+        let (stop_tx, stop_rx) = mpsc::channel();
+        let handle = std::thread::spawn(move || {
+            Supervisor {
+                matcher,
+                event_tx,
+                event_filter,
+                backoff,
+                stop_rx,
+            }
+            .run();
+        });
+
+        Ok(Self { stop_tx, handle })
+    }
+
+    /// Stops the supervisor and the observer that is currently attached.
+    ///
+    /// # Notes
+    /// If you don't call this method, the supervisor will stop in the background when dropped.
+    pub fn stop(self) {
+        let _ = self.stop_tx.send(());
+        let _ = self.handle.join();
+    }
+}
+
+/// Holds the events of a new observer until it is opened, so that [`Event::ProcessAttached`]
+/// and [`ObserverEvent::Recovered`] precede the window events of the observer.
+#[derive(Clone)]
+struct Gate {
+    event_tx: EventSender,
+    /// The events that have been held. It is `None` once the gate is opened.
+    held: Arc<Mutex<Option<Vec<EventResult>>>>,
+}
+
+impl Gate {
+    fn new(event_tx: EventSender) -> Self {
+        Self {
+            event_tx,
+            held: Arc::new(Mutex::new(Some(Vec::new()))),
+        }
+    }
+
+    /// Sends the held events and lets the next events through.
+    fn open(&self) {
+        // The lock is held while sending so that the next events don't overtake the held ones.
+        let mut held = self.held.lock().unwrap_or_else(|e| e.into_inner());
+
+        for result in held.take().into_iter().flatten() {
+            self.event_tx.send(result);
+        }
+    }
+}
+
+impl EventSink for Gate {
+    fn send(&mut self, result: EventResult) -> bool {
+        let mut held = self.held.lock().unwrap_or_else(|e| e.into_inner());
+
+        match held.as_mut() {
+            Some(held) => {
+                held.push(result);
+                true
+            }
+            None => self.event_tx.send(result),
+        }
+    }
+}
+
+/// Why the supervisor stopped the observer.
+enum Detachment {
+    Exited,
+    Failed,
+    Stopped,
+}
+
+struct Supervisor {
+    matcher: ProcessMatcher,
+    event_tx: EventSender,
+    event_filter: EventFilter,
+    backoff: Backoff,
+    stop_rx: mpsc::Receiver<()>,
+}
+
+impl Supervisor {
+    /// Waits for the given duration. It returns `false` if the supervisor should stop.
+    fn wait(&self, duration: Duration) -> bool {
+        matches!(
+            self.stop_rx.recv_timeout(duration),
+            Err(RecvTimeoutError::Timeout)
+        )
+    }
+
+    fn dispatch(&self, event: Event) {
+        if self.event_filter.should_dispatch(&event) {
+            let _ = self
+                .event_tx
+                .send(Ok(MaybeWindowAvailable::NotAvailable { event }));
+        }
+    }
+
+    /// Starts an observer of the process. Its events are held by the returned [`Gate`]
+    /// until it is opened.
+    fn attach(&self, pid: u32) -> Option<(WindowObserver, Gate)> {
+        let gate = Gate::new(self.event_tx.clone());
+
+        // The application may not be ready yet just after it is launched,
+        // so errors are not reported and attaching is retried on the next poll.
+        let observer =
+            block_on(WindowObserver::start(pid, gate.clone(), self.event_filter)).ok()?;
+
+        // A backend that has already ended is not attached,
+        // so that it is not reported as recovered.
+        if !observer.is_running() {
+            let _ = block_on(observer.stop());
+            return None;
+        }
+
+        Some((observer, gate))
+    }
+
+    fn run(self) {
+        let mut failures = 0;
+        // The process whose observer is being restarted after its backend has ended.
+        let mut restarting = None;
+
+        loop {
+            let Some(pid) = self.matcher.find() else {
+                if self.wait(POLL_INTERVAL) {
+                    continue;
+                }
+                return;
+            };
+
+            let Some((observer, gate)) = self.attach(pid) else {
+                let delay = self.backoff.delay(failures);
+                failures = failures.saturating_add(1);
+
+                if self.wait(delay) {
+                    continue;
+                }
+                return;
+            };
+            let attached_at = Instant::now();
+
+            if restarting.take() == Some(pid) {
+                self.dispatch(Event::Lifecycle {
+                    event: ObserverEvent::Recovered,
+                });
+            } else {
+                self.dispatch(Event::ProcessAttached { pid });
+            }
+            gate.open();
+
+            let detachment = loop {
+                if !is_process_running(pid as _) {
+                    break Detachment::Exited;
+                }
+                if !observer.is_running() {
+                    break Detachment::Failed;
+                }
+                if !self.wait(POLL_INTERVAL) {
+                    break Detachment::Stopped;
+                }
+            };
+
+            let _ = block_on(observer.stop());
+
+            match detachment {
+                Detachment::Exited => {
+                    failures = 0;
+                    self.dispatch(Event::ProcessDetached { pid });
+                }
+                Detachment::Failed => {
+                    if attached_at.elapsed() >= self.backoff.max {
+                        failures = 0;
+                    }
+                    let delay = self.backoff.delay(failures);
+                    failures = failures.saturating_add(1);
+
+                    if !self.wait(delay) {
+                        return;
+                    }
+
+                    if is_process_running(pid as _) {
+                        restarting = Some(pid);
+                    } else {
+                        failures = 0;
+                        self.dispatch(Event::ProcessDetached { pid });
+                    }
+                }
+                Detachment::Stopped => return,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn not_available(event: Event) -> EventResult {
+        Ok(MaybeWindowAvailable::NotAvailable { event })
+    }
+
+    fn event(result: EventResult) -> Event {
+        result.unwrap().event().clone()
+    }
+
+    #[test]
+    fn backoff_grows_up_to_max() {
+        let backoff = Backoff {
+            initial: Duration::from_secs(1),
+            max: Duration::from_secs(10),
+            multiplier: 2.,
+        };
+
+        assert_eq!(backoff.delay(0), Duration::from_secs(1));
+        assert_eq!(backoff.delay(1), Duration::from_secs(2));
+        assert_eq!(backoff.delay(3), Duration::from_secs(8));
+        assert_eq!(backoff.delay(4), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn backoff_clamps_overflow_to_max() {
+        let backoff = Backoff::default();
+
+        assert_eq!(backoff.delay(u32::MAX), backoff.max);
+
+        let huge = Backoff {
+            multiplier: f64::MAX,
+            ..backoff
+        };
+        assert_eq!(huge.delay(2), huge.max);
+    }
+
+    #[test]
+    fn backoff_never_shrinks() {
+        let backoff = Backoff {
+            multiplier: 0.5,
+            ..Backoff::default()
+        };
+
+        assert_eq!(backoff.delay(5), backoff.initial);
+    }
+
+    #[test]
+    fn gate_holds_events_until_opened() {
+        let (event_tx, event_rx) = mpsc::channel();
+        let event_tx = EventSender::from(event_tx);
+        let mut gate = Gate::new(event_tx.clone());
+
+        assert!(gate.send(not_available(Event::InitialStateCompleted)));
+        assert!(event_rx.try_recv().is_err());
+
+        event_tx.send(not_available(Event::ProcessAttached { pid: 1 }));
+        gate.open();
+        gate.send(not_available(Event::ProcessDetached { pid: 1 }));
+
+        let events: Vec<_> = event_rx.try_iter().map(event).collect();
+        assert_eq!(
+            events,
+            [
+                Event::ProcessAttached { pid: 1 },
+                Event::InitialStateCompleted,
+                Event::ProcessDetached { pid: 1 },
+            ]
+        );
+    }
+
+    #[test]
+    fn name_ignores_ascii_case() {
+        let matcher = ProcessMatcher::name("Notepad.exe");
+
+        assert!(matcher.matches(1, "notepad.exe"));
+        assert!(matcher.matches(1, "NOTEPAD.EXE"));
+        assert!(!matcher.matches(1, "notepad"));
+    }
+
+    #[test]
+    fn predicate_receives_pid_and_name() {
+        let matcher = ProcessMatcher::predicate(|pid, name| pid == 42 && name == "app");
+
+        assert!(matcher.matches(42, "app"));
+        assert!(!matcher.matches(41, "app"));
+    }
+}
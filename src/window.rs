@@ -1,6 +1,36 @@
+use std::ops::{Add, AddAssign, Div, Mul, Sub, SubAssign};
+
 use window_getter::Bounds;
 
-use crate::{Error, platform_impl::PlatformWindow};
+#[cfg(target_os = "windows")]
+use crate::platform_impl::windows::window_control;
+use crate::{Error, Monitor, platform_impl::PlatformWindow};
+
+/// An action on a window. It is reported by [`Error::UnsupportedAction`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowAction {
+    /// [`Window::set_position`].
+    SetPosition,
+    /// [`Window::set_size`].
+    SetSize,
+    /// [`Window::set_bounds`].
+    SetBounds,
+    /// [`Window::focus`].
+    Focus,
+    /// [`Window::raise`].
+    Raise,
+    /// [`Window::minimize`].
+    Minimize,
+    /// [`Window::restore`].
+    Restore,
+    /// [`Window::maximize`].
+    Maximize,
+    /// [`Window::set_fullscreen`].
+    SetFullscreen,
+    /// [`Window::close`].
+    Close,
+}
 
 /// A wrapper around platform-specific window implementations.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -42,11 +72,7 @@ impl Window {
         }
         #[cfg(target_os = "windows")]
         {
-            Ok(self
-                .0
-                .visible_bounds()
-                .map_err(|e| Error::PlatformSpecificError(e.into()))?
-                .into())
+            Ok(self.bounds()?.size())
         }
     }
 
@@ -57,6 +83,64 @@ impl Window {
             Ok(self.0.position()?)
         }
         #[cfg(target_os = "windows")]
+        {
+            Ok(self.bounds()?.position())
+        }
+    }
+
+    /// Retrieves the display that shows the largest part of the window.
+    /// If the window is on no display, the display nearest to it is returned.
+    /// It returns `None` if no display is found.
+    pub fn current_monitor(&self) -> Result<Option<Monitor>, Error> {
+        Ok(Monitor::dominant_for(&self.bounds()?))
+    }
+
+    /// Retrieves the bounds of the window in the given coordinate space.
+    /// See [`CoordinateSpace`] for details.
+    pub fn bounds_in(&self, space: CoordinateSpace) -> Result<Rect, Error> {
+        let bounds = self.bounds()?;
+
+        if space == CoordinateSpace::native() {
+            Ok(bounds)
+        } else {
+            Ok(bounds.convert(CoordinateSpace::native(), space, self.scale_factor()?))
+        }
+    }
+
+    /// Retrieves the number of physical pixels per logical pixel of the display of the window.
+    ///
+    /// # Platform-specific
+    /// - **macOS:** It is the scale factor of the display that contains the center of the window.
+    /// - **Windows:** It is the DPI of the window divided by 96.
+    pub fn scale_factor(&self) -> Result<f64, Error> {
+        #[cfg(target_os = "macos")]
+        {
+            let center = self.bounds()?.center();
+            let display = crate::platform_impl::macos::display::display_at(center);
+
+            Ok(crate::platform_impl::macos::display::scale_factor(display))
+        }
+        #[cfg(target_os = "windows")]
+        {
+            Ok(crate::platform_impl::windows::window_state::scale_factor(
+                self.0.hwnd(),
+            )?)
+        }
+    }
+
+    /// Retrieves the position and the size of the window together.
+    ///
+    /// They are read in a single query, so they are consistent with each other
+    /// unlike calling [`position`](Self::position) and [`size`](Self::size) separately.
+    ///
+    /// # Platform-specific
+    /// - **Windows:** The bounds don't include the invisible resize borders around the window.
+    pub fn bounds(&self) -> Result<Rect, Error> {
+        #[cfg(target_os = "macos")]
+        {
+            Ok(self.0.bounds()?)
+        }
+        #[cfg(target_os = "windows")]
         {
             Ok(self
                 .0
@@ -81,6 +165,280 @@ impl Window {
         }
     }
 
+    /// Checks if the window is minimized.
+    pub fn is_minimized(&self) -> Result<bool, Error> {
+        #[cfg(target_os = "macos")]
+        {
+            Ok(self.0.is_minimized()?)
+        }
+        #[cfg(target_os = "windows")]
+        {
+            Ok(crate::platform_impl::windows::window_state::is_minimized(
+                self.0.hwnd(),
+            ))
+        }
+    }
+
+    /// Checks if the window is maximized.
+    ///
+    /// # Platform-specific
+    /// - **macOS:** It always returns `false` because macOS has no maximized state.
+    pub fn is_maximized(&self) -> Result<bool, Error> {
+        #[cfg(target_os = "macos")]
+        {
+            Ok(false)
+        }
+        #[cfg(target_os = "windows")]
+        {
+            Ok(crate::platform_impl::windows::window_state::is_maximized(
+                self.0.hwnd(),
+            ))
+        }
+    }
+
+    /// Checks if the window is in the fullscreen mode.
+    ///
+    /// # Platform-specific
+    /// - **Windows:** A window is regarded as fullscreen when it covers its whole monitor
+    ///   without being maximized.
+    pub fn is_fullscreen(&self) -> Result<bool, Error> {
+        #[cfg(target_os = "macos")]
+        {
+            Ok(self.0.is_fullscreen()?)
+        }
+        #[cfg(target_os = "windows")]
+        {
+            Ok(crate::platform_impl::windows::window_state::is_fullscreen(
+                self.0.hwnd(),
+            )?)
+        }
+    }
+
+    /// Checks if the window is visible. A minimized window is not visible.
+    ///
+    /// # Platform-specific
+    /// - **macOS:** The windows of a hidden application are not visible.
+    pub fn is_visible(&self) -> Result<bool, Error> {
+        #[cfg(target_os = "macos")]
+        {
+            Ok(self.0.is_visible()?)
+        }
+        #[cfg(target_os = "windows")]
+        {
+            Ok(crate::platform_impl::windows::window_state::is_visible(
+                self.0.hwnd(),
+            ))
+        }
+    }
+
+    /// Retrieves the ID of the process that owns the window.
+    pub fn pid(&self) -> Result<u32, Error> {
+        #[cfg(target_os = "macos")]
+        {
+            Ok(self.0.pid()?)
+        }
+        #[cfg(target_os = "windows")]
+        {
+            self.0
+                .owner_pid()
+                .map_err(|e| Error::PlatformSpecificError(e.into()))
+        }
+    }
+
+    /// Retrieves the position of the window in the stacking order of the windows of its process.
+    /// `0` is the frontmost window. It returns `None` if the window is not in the order.
+    ///
+    /// # Platform-specific
+    /// - **macOS:** It is the order of the windows reported by the Accessibility API
+    ///   for the application, which follows the order on screen.
+    /// - **Windows:** Only the visible top-level windows are ordered.
+    ///   The minimized windows are included.
+    pub fn z_index(&self) -> Result<Option<usize>, Error> {
+        #[cfg(target_os = "macos")]
+        {
+            Ok(self.0.z_index()?)
+        }
+        #[cfg(target_os = "windows")]
+        {
+            let pid = self
+                .0
+                .owner_pid()
+                .map_err(|e| Error::PlatformSpecificError(e.into()))?;
+
+            Ok(
+                crate::platform_impl::windows::window_state::stacking_order(pid)
+                    .iter()
+                    .position(|hwnd| *hwnd == self.0.hwnd()),
+            )
+        }
+    }
+
+    /// Moves the window to the given position.
+    pub fn set_position(&self, position: Position) -> Result<(), Error> {
+        #[cfg(target_os = "macos")]
+        {
+            Ok(self.0.set_position(position)?)
+        }
+        #[cfg(target_os = "windows")]
+        {
+            Ok(window_control::set_bounds(
+                self.0.hwnd(),
+                Some(position),
+                None,
+            )?)
+        }
+    }
+
+    /// Resizes the window to the given size.
+    pub fn set_size(&self, size: Size) -> Result<(), Error> {
+        #[cfg(target_os = "macos")]
+        {
+            Ok(self.0.set_size(size)?)
+        }
+        #[cfg(target_os = "windows")]
+        {
+            Ok(window_control::set_bounds(self.0.hwnd(), None, Some(size))?)
+        }
+    }
+
+    /// Moves and resizes the window at once.
+    ///
+    /// # Platform-specific
+    /// - **macOS:** The window is moved and then resized,
+    ///   so the size may be limited by the screen at the new position.
+    pub fn set_bounds(&self, position: Position, size: Size) -> Result<(), Error> {
+        #[cfg(target_os = "macos")]
+        {
+            self.0.set_position(position)?;
+            Ok(self.0.set_size(size)?)
+        }
+        #[cfg(target_os = "windows")]
+        {
+            Ok(window_control::set_bounds(
+                self.0.hwnd(),
+                Some(position),
+                Some(size),
+            )?)
+        }
+    }
+
+    /// Brings the window to the front and gives it the input focus.
+    ///
+    /// # Platform-specific
+    /// - **macOS:** The application of the window is activated.
+    /// - **Windows:** The window is restored if it is minimized.
+    ///   Windows may refuse to change the foreground window,
+    ///   and the window flashes on the taskbar instead in that case.
+    pub fn focus(&self) -> Result<(), Error> {
+        #[cfg(target_os = "macos")]
+        {
+            Ok(self.0.focus()?)
+        }
+        #[cfg(target_os = "windows")]
+        {
+            window_control::focus(self.0.hwnd());
+            Ok(())
+        }
+    }
+
+    /// Brings the window to the front without giving it the input focus.
+    ///
+    /// # Platform-specific
+    /// - **macOS:** The window is brought to the front of the other windows of its application only.
+    pub fn raise(&self) -> Result<(), Error> {
+        #[cfg(target_os = "macos")]
+        {
+            Ok(self.0.raise()?)
+        }
+        #[cfg(target_os = "windows")]
+        {
+            Ok(window_control::raise(self.0.hwnd())?)
+        }
+    }
+
+    /// Minimizes the window.
+    pub fn minimize(&self) -> Result<(), Error> {
+        #[cfg(target_os = "macos")]
+        {
+            Ok(self.0.set_minimized(true)?)
+        }
+        #[cfg(target_os = "windows")]
+        {
+            window_control::minimize(self.0.hwnd());
+            Ok(())
+        }
+    }
+
+    /// Restores the window from the minimized state.
+    ///
+    /// # Platform-specific
+    /// - **Windows:** It also restores the window from the maximized state.
+    pub fn restore(&self) -> Result<(), Error> {
+        #[cfg(target_os = "macos")]
+        {
+            Ok(self.0.set_minimized(false)?)
+        }
+        #[cfg(target_os = "windows")]
+        {
+            window_control::restore(self.0.hwnd());
+            Ok(())
+        }
+    }
+
+    /// Maximizes the window.
+    ///
+    /// # Platform-specific
+    /// - **macOS:** It will always return [`Error::UnsupportedAction`]
+    ///   because macOS has no maximized state.
+    pub fn maximize(&self) -> Result<(), Error> {
+        #[cfg(target_os = "macos")]
+        {
+            Err(Error::UnsupportedAction(WindowAction::Maximize))
+        }
+        #[cfg(target_os = "windows")]
+        {
+            window_control::maximize(self.0.hwnd());
+            Ok(())
+        }
+    }
+
+    /// Enters or exits the fullscreen mode.
+    ///
+    /// # Platform-specific
+    /// - **Windows:** It will always return [`Error::UnsupportedAction`]
+    ///   because the fullscreen mode is implemented by each application.
+    pub fn set_fullscreen(&self, fullscreen: bool) -> Result<(), Error> {
+        #[cfg(target_os = "macos")]
+        {
+            Ok(self.0.set_fullscreen(fullscreen)?)
+        }
+        #[cfg(target_os = "windows")]
+        {
+            let _ = fullscreen;
+            Err(Error::UnsupportedAction(WindowAction::SetFullscreen))
+        }
+    }
+
+    /// Asks the window to close as if the user clicked its close button.
+    /// The application may ask the user to confirm it, so the window may not be closed.
+    ///
+    /// # Platform-specific
+    /// - **macOS:** It returns [`Error::UnsupportedAction`] if the window has no close button.
+    pub fn close(&self) -> Result<(), Error> {
+        #[cfg(target_os = "macos")]
+        {
+            if self.0.close()? {
+                Ok(())
+            } else {
+                Err(Error::UnsupportedAction(WindowAction::Close))
+            }
+        }
+        #[cfg(target_os = "windows")]
+        {
+            Ok(window_control::close(self.0.hwnd())?)
+        }
+    }
+
     /// Retrieves the unique identifier of the window.
     ///
     /// # Platform-specific
@@ -127,7 +485,10 @@ impl Window {
 }
 
 /// Represents the size of a window.
-#[derive(Debug, Clone, Default, PartialEq)]
+/// See [`CoordinateSpace`] for the unit.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Size {
     /// The width of the window.
     pub width: f64,
@@ -136,7 +497,10 @@ pub struct Size {
 }
 
 /// Represents the position of a window.
+/// See [`CoordinateSpace`] for the origin and the unit.
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Position {
     /// The x-coordinate of the window.
     pub x: f64,
@@ -161,3 +525,558 @@ impl From<Bounds> for Position {
         }
     }
 }
+
+impl From<Rect> for Size {
+    fn from(value: Rect) -> Self {
+        value.size()
+    }
+}
+
+impl From<Rect> for Position {
+    fn from(value: Rect) -> Self {
+        value.position()
+    }
+}
+
+/// Implements the component-wise arithmetic operators for a pair of `f64`.
+macro_rules! impl_ops {
+    ($ty:ident, $a:ident, $b:ident) => {
+        impl Add for $ty {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self {
+                    $a: self.$a + rhs.$a,
+                    $b: self.$b + rhs.$b,
+                }
+            }
+        }
+
+        impl Sub for $ty {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self {
+                    $a: self.$a - rhs.$a,
+                    $b: self.$b - rhs.$b,
+                }
+            }
+        }
+
+        impl Mul<f64> for $ty {
+            type Output = Self;
+
+            fn mul(self, rhs: f64) -> Self {
+                Self {
+                    $a: self.$a * rhs,
+                    $b: self.$b * rhs,
+                }
+            }
+        }
+
+        impl Div<f64> for $ty {
+            type Output = Self;
+
+            fn div(self, rhs: f64) -> Self {
+                Self {
+                    $a: self.$a / rhs,
+                    $b: self.$b / rhs,
+                }
+            }
+        }
+
+        impl AddAssign for $ty {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl SubAssign for $ty {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+    };
+}
+
+impl_ops!(Size, width, height);
+impl_ops!(Position, x, y);
+
+impl Mul<f64> for Rect {
+    type Output = Self;
+
+    fn mul(self, rhs: f64) -> Self {
+        Rect::new(self.position() * rhs, self.size() * rhs)
+    }
+}
+
+impl Div<f64> for Rect {
+    type Output = Self;
+
+    fn div(self, rhs: f64) -> Self {
+        Rect::new(self.position() / rhs, self.size() / rhs)
+    }
+}
+
+/// The unit of the coordinates of [`Position`], [`Size`] and [`Rect`].
+///
+/// On every backend, the origin is the top-left corner of the primary display
+/// and the y-axis points down. Only the unit differs between the backends,
+/// and [`CoordinateSpace::native`] tells which one the values returned by the crate use.
+/// Use [`Window::bounds_in`] or the `convert` methods to get the values in a specific unit.
+///
+/// # Notes
+/// A conversion multiplies or divides the coordinates by one scale factor.
+/// When the displays have different scale factors, global coordinates converted
+/// with the scale factor of one display are exact only for the windows on that display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum CoordinateSpace {
+    /// Pixels that are independent of the pixel density of the display,
+    /// such as points on macOS and device-independent pixels on Windows.
+    Logical,
+    /// Pixels of the display.
+    Physical,
+}
+
+impl CoordinateSpace {
+    /// Returns the coordinate space of the values that the backend of the current platform returns.
+    ///
+    /// # Platform-specific
+    /// - **macOS:** It is [`CoordinateSpace::Logical`] because the Accessibility API uses points.
+    /// - **Windows:** It is [`CoordinateSpace::Physical`].
+    ///   The process must be per-monitor DPI aware, otherwise Windows scales the coordinates
+    ///   for the process and they are neither logical nor physical.
+    pub const fn native() -> Self {
+        #[cfg(target_os = "macos")]
+        {
+            Self::Logical
+        }
+        #[cfg(target_os = "windows")]
+        {
+            Self::Physical
+        }
+    }
+}
+
+/// Implements the conversions between the coordinate spaces.
+macro_rules! impl_space_conversions {
+    ($ty:ident) => {
+        impl $ty {
+            /// Converts logical coordinates into physical ones.
+            pub fn to_physical(self, scale_factor: f64) -> Self {
+                self * scale_factor
+            }
+
+            /// Converts physical coordinates into logical ones.
+            pub fn to_logical(self, scale_factor: f64) -> Self {
+                self / scale_factor
+            }
+
+            /// Converts the coordinates from a coordinate space into another one.
+            pub fn convert(
+                self,
+                from: CoordinateSpace,
+                to: CoordinateSpace,
+                scale_factor: f64,
+            ) -> Self {
+                match (from, to) {
+                    (CoordinateSpace::Logical, CoordinateSpace::Physical) => {
+                        self.to_physical(scale_factor)
+                    }
+                    (CoordinateSpace::Physical, CoordinateSpace::Logical) => {
+                        self.to_logical(scale_factor)
+                    }
+                    _ => self,
+                }
+            }
+        }
+    };
+}
+
+impl_space_conversions!(Size);
+impl_space_conversions!(Position);
+impl_space_conversions!(Rect);
+
+/// Represents the bounds of a window, which is its position and size.
+///
+/// The y-axis points down, so [`top`](Self::top) is [`y`](Self::y)
+/// and [`bottom`](Self::bottom) is `y + height`.
+/// See [`CoordinateSpace`] for the origin and the unit.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Rect {
+    /// The x-coordinate of the left edge.
+    pub x: f64,
+    /// The y-coordinate of the top edge.
+    pub y: f64,
+    /// The width of the rectangle.
+    pub width: f64,
+    /// The height of the rectangle.
+    pub height: f64,
+}
+
+impl Rect {
+    /// Creates a new `Rect` from its position and size.
+    pub fn new(position: Position, size: Size) -> Self {
+        Self {
+            x: position.x,
+            y: position.y,
+            width: size.width,
+            height: size.height,
+        }
+    }
+
+    /// Returns the position of the top-left corner.
+    pub fn position(&self) -> Position {
+        Position {
+            x: self.x,
+            y: self.y,
+        }
+    }
+
+    /// Returns the size.
+    pub fn size(&self) -> Size {
+        Size {
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Returns the x-coordinate of the left edge.
+    pub fn left(&self) -> f64 {
+        self.x
+    }
+
+    /// Returns the y-coordinate of the top edge.
+    pub fn top(&self) -> f64 {
+        self.y
+    }
+
+    /// Returns the x-coordinate of the right edge.
+    pub fn right(&self) -> f64 {
+        self.x + self.width
+    }
+
+    /// Returns the y-coordinate of the bottom edge.
+    pub fn bottom(&self) -> f64 {
+        self.y + self.height
+    }
+
+    /// Returns the area.
+    pub fn area(&self) -> f64 {
+        self.width * self.height
+    }
+
+    /// Returns `true` if the area is zero or negative.
+    pub fn is_empty(&self) -> bool {
+        self.width <= 0. || self.height <= 0.
+    }
+
+    /// Returns the center point.
+    pub fn center(&self) -> Position {
+        Position {
+            x: self.x + self.width / 2.,
+            y: self.y + self.height / 2.,
+        }
+    }
+
+    /// Checks if the point is inside the rectangle.
+    /// The left and top edges are inside, and the right and bottom edges are outside.
+    pub fn contains(&self, point: Position) -> bool {
+        self.left() <= point.x
+            && point.x < self.right()
+            && self.top() <= point.y
+            && point.y < self.bottom()
+    }
+
+    /// Checks if the other rectangle is entirely inside this rectangle.
+    pub fn contains_rect(&self, other: &Rect) -> bool {
+        self.left() <= other.left()
+            && other.right() <= self.right()
+            && self.top() <= other.top()
+            && other.bottom() <= self.bottom()
+    }
+
+    /// Checks if the rectangles overlap with a positive area.
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Returns the overlapping area of the rectangles, or `None` if they don't overlap.
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let left = self.left().max(other.left());
+        let top = self.top().max(other.top());
+        let right = self.right().min(other.right());
+        let bottom = self.bottom().min(other.bottom());
+
+        (left < right && top < bottom).then_some(Rect {
+            x: left,
+            y: top,
+            width: right - left,
+            height: bottom - top,
+        })
+    }
+
+    /// Returns the parts of this rectangle that are not covered by the other rectangle.
+    ///
+    /// The parts don't overlap each other. There are at most four of them:
+    /// the full-width bands above and below the other rectangle, and the parts on its left and right.
+    pub fn subtract(&self, other: &Rect) -> Vec<Rect> {
+        let Some(covered) = self.intersection(other) else {
+            return if self.is_empty() {
+                Vec::new()
+            } else {
+                vec![*self]
+            };
+        };
+
+        let bands = [
+            // Above the covered part.
+            (self.left(), self.top(), self.right(), covered.top()),
+            // Below the covered part.
+            (self.left(), covered.bottom(), self.right(), self.bottom()),
+            // On the left of the covered part.
+            (self.left(), covered.top(), covered.left(), covered.bottom()),
+            // On the right of the covered part.
+            (
+                covered.right(),
+                covered.top(),
+                self.right(),
+                covered.bottom(),
+            ),
+        ];
+
+        bands
+            .into_iter()
+            .map(|(left, top, right, bottom)| Rect {
+                x: left,
+                y: top,
+                width: right - left,
+                height: bottom - top,
+            })
+            .filter(|part| !part.is_empty())
+            .collect()
+    }
+
+    /// Returns the smallest rectangle that contains both rectangles.
+    /// An empty rectangle is ignored because it contains nothing.
+    pub fn union(&self, other: &Rect) -> Rect {
+        if other.is_empty() {
+            return *self;
+        }
+        if self.is_empty() {
+            return *other;
+        }
+
+        let left = self.left().min(other.left());
+        let top = self.top().min(other.top());
+
+        Rect {
+            x: left,
+            y: top,
+            width: self.right().max(other.right()) - left,
+            height: self.bottom().max(other.bottom()) - top,
+        }
+    }
+
+    /// Returns the rectangle shrunk by `dx` on the left and right edges
+    /// and by `dy` on the top and bottom edges.
+    /// Negative values grow the rectangle. The size never becomes negative.
+    pub fn inset(&self, dx: f64, dy: f64) -> Rect {
+        let width = (self.width - dx * 2.).max(0.);
+        let height = (self.height - dy * 2.).max(0.);
+        let center = self.center();
+
+        Rect {
+            x: center.x - width / 2.,
+            y: center.y - height / 2.,
+            width,
+            height,
+        }
+    }
+
+    /// Returns the distance from the point to the nearest point of the rectangle.
+    /// It is zero if the point is inside the rectangle.
+    pub fn distance_to(&self, point: Position) -> f64 {
+        let dx = (self.left() - point.x).max(point.x - self.right()).max(0.);
+        let dy = (self.top() - point.y).max(point.y - self.bottom()).max(0.);
+
+        dx.hypot(dy)
+    }
+}
+
+impl From<Bounds> for Rect {
+    fn from(value: Bounds) -> Self {
+        Rect {
+            x: value.x,
+            y: value.y,
+            width: value.width,
+            height: value.height,
+        }
+    }
+}
+
+impl From<Rect> for Bounds {
+    fn from(value: Rect) -> Self {
+        Bounds {
+            x: value.x,
+            y: value.y,
+            width: value.width,
+            height: value.height,
+        }
+    }
+}
+
+/// Creates a window that is distinguished by `id` without asking the OS about it.
+#[cfg(test)]
+pub(crate) fn test_window(id: u32) -> Window {
+    #[cfg(target_os = "macos")]
+    let platform_window =
+        PlatformWindow::new(accessibility::AXUIElement::application(id as libc::pid_t));
+    #[cfg(target_os = "windows")]
+    let platform_window =
+        PlatformWindow::new(windows::Win32::Foundation::HWND(id as usize as *mut _));
+
+    Window::new(platform_window)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: f64, y: f64, width: f64, height: f64) -> Rect {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn intersection_of_touching_rects_is_none() {
+        let a = rect(0., 0., 10., 10.);
+
+        assert_eq!(a.intersection(&rect(10., 0., 10., 10.)), None);
+        assert_eq!(a.intersection(&rect(0., 10., 10., 10.)), None);
+        assert!(!a.intersects(&rect(10., 10., 1., 1.)));
+    }
+
+    #[test]
+    fn intersection_of_contained_rect_is_the_rect() {
+        let outer = rect(0., 0., 10., 10.);
+        let inner = rect(2., 3., 4., 5.);
+
+        assert_eq!(outer.intersection(&inner), Some(inner));
+        assert_eq!(inner.intersection(&outer), Some(inner));
+        assert!(outer.contains_rect(&inner));
+        assert!(!inner.contains_rect(&outer));
+    }
+
+    #[test]
+    fn intersection_with_empty_rect_is_none() {
+        let a = rect(0., 0., 10., 10.);
+
+        assert_eq!(a.intersection(&rect(5., 5., 0., 0.)), None);
+        assert_eq!(a.intersection(&rect(5., 5., -2., 3.)), None);
+    }
+
+    #[test]
+    fn subtract_touching_rect_keeps_the_rect() {
+        let a = rect(0., 0., 10., 10.);
+
+        assert_eq!(a.subtract(&rect(10., 0., 10., 10.)), [a]);
+    }
+
+    #[test]
+    fn subtract_from_empty_rect_is_empty() {
+        let empty = rect(0., 0., 0., 10.);
+
+        assert!(empty.subtract(&rect(20., 20., 1., 1.)).is_empty());
+        assert!(empty.subtract(&empty).is_empty());
+    }
+
+    #[test]
+    fn subtract_contained_rect_leaves_four_parts() {
+        let outer = rect(0., 0., 10., 10.);
+        let parts = outer.subtract(&rect(2., 2., 6., 6.));
+
+        assert_eq!(
+            parts,
+            [
+                rect(0., 0., 10., 2.),
+                rect(0., 8., 10., 2.),
+                rect(0., 2., 2., 6.),
+                rect(8., 2., 2., 6.),
+            ]
+        );
+        assert_eq!(parts.iter().map(Rect::area).sum::<f64>(), 100. - 36.);
+    }
+
+    #[test]
+    fn subtract_covering_rect_is_empty() {
+        let inner = rect(2., 2., 6., 6.);
+
+        assert!(inner.subtract(&rect(0., 0., 10., 10.)).is_empty());
+        assert!(inner.subtract(&inner).is_empty());
+    }
+
+    #[test]
+    fn union_contains_both_rects() {
+        let a = rect(0., 0., 10., 10.);
+        let b = rect(20., -5., 5., 5.);
+        let union = a.union(&b);
+
+        assert_eq!(union, rect(0., -5., 25., 15.));
+        assert!(union.contains_rect(&a) && union.contains_rect(&b));
+        assert_eq!(a.union(&rect(2., 2., 1., 1.)), a);
+    }
+
+    #[test]
+    fn union_ignores_empty_rect() {
+        let a = rect(0., 0., 10., 10.);
+        let empty = rect(100., 100., 0., 0.);
+
+        assert_eq!(a.union(&empty), a);
+        assert_eq!(empty.union(&a), a);
+    }
+
+    #[test]
+    fn inset_shrinks_around_the_center() {
+        let a = rect(0., 0., 10., 20.);
+
+        assert_eq!(a.inset(1., 2.), rect(1., 2., 8., 16.));
+        assert_eq!(a.inset(-1., -1.), rect(-1., -1., 12., 22.));
+    }
+
+    #[test]
+    fn inset_never_makes_a_negative_size() {
+        let a = rect(0., 0., 10., 20.);
+        let collapsed = a.inset(6., 15.);
+
+        assert_eq!(collapsed, rect(5., 10., 0., 0.));
+        assert!(collapsed.is_empty());
+        assert_eq!(collapsed.center(), a.center());
+    }
+
+    #[test]
+    fn distance_to_is_zero_inside_and_on_the_edges() {
+        let a = rect(0., 0., 10., 10.);
+
+        assert_eq!(a.distance_to(Position { x: 5., y: 5. }), 0.);
+        assert_eq!(a.distance_to(Position { x: 10., y: 10. }), 0.);
+    }
+
+    #[test]
+    fn distance_to_measures_to_the_nearest_edge_or_corner() {
+        let a = rect(0., 0., 10., 10.);
+
+        assert_eq!(a.distance_to(Position { x: 15., y: 5. }), 5.);
+        assert_eq!(a.distance_to(Position { x: 5., y: -2. }), 2.);
+        assert_eq!(a.distance_to(Position { x: 13., y: 14. }), 5.);
+    }
+}
@@ -0,0 +1,480 @@
+//! Serializable representations of the events for storing them or sending them to other processes.
+//!
+//! [`Window`] is a live handle of a window, so it can't be serialized.
+//! [`EventRecord`] replaces it with a [`WindowSnapshot`] of its properties
+//! and [`Event`] with a [`WireEvent`].
+//!
+//! The enums are tagged by the `"kind"` or the `"type"` field, and the tags and the fields
+//! are in snake_case. They are kept stable so that the other programs can rely on them.
+//! Use [`json_schema`] with the `schema` feature to generate a JSON Schema of [`EventRecord`].
+//!
+//! # Example
+//! ```
+//! use window_observer::{EventResult, wire::EventRecord};
+//!
+//! fn to_record(result: &EventResult) -> EventRecord {
+//!     // The record can be serialized with any format of serde, such as JSON.
+//!     EventRecord::capture_result(result)
+//! }
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ErrorKind, Event, EventError, EventResult, MaybeWindowAvailable, Monitor, ObserverEvent, Rect,
+    Window,
+};
+
+/// The state of a window in a [`WindowSnapshot`].
+/// A state that could not be retrieved is `false`.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct WindowState {
+    /// Whether the window is minimized.
+    pub minimized: bool,
+    /// Whether the window is maximized.
+    ///
+    /// # Platform-specific
+    /// - **macOS:** It is always `false` because macOS has no maximized state.
+    pub maximized: bool,
+    /// Whether the window is in the fullscreen mode.
+    pub fullscreen: bool,
+    /// Whether the window is visible.
+    pub visible: bool,
+}
+
+/// The properties of a window at the time it was captured.
+/// A property that could not be retrieved is `None`, for example because the window was closed.
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct WindowSnapshot {
+    /// The ID of the window. See [`window_getter::WindowId::as_u32`] for the value.
+    ///
+    /// # Platform-specific
+    /// - **macOS:** It is always `None` without the `macos-private-api` feature.
+    pub id: Option<u32>,
+    /// The ID of the process that owns the window.
+    pub pid: Option<u32>,
+    /// The title of the window.
+    pub title: Option<String>,
+    /// The bounds of the window in the native coordinate space.
+    /// See [`CoordinateSpace::native`](crate::CoordinateSpace::native).
+    pub bounds: Option<Rect>,
+    /// Whether the window is focused.
+    pub focused: bool,
+    /// The state of the window.
+    pub state: WindowState,
+}
+
+impl WindowSnapshot {
+    /// Captures the current properties of the window.
+    pub fn capture(window: &Window) -> Self {
+        Self {
+            id: crate::registry::window_id(window).map(|id| id.as_u32()),
+            pid: window.pid().ok(),
+            title: window.title().ok().flatten(),
+            bounds: window.bounds().ok(),
+            focused: window.is_focused().unwrap_or(false),
+            state: WindowState {
+                minimized: window.is_minimized().unwrap_or(false),
+                maximized: window.is_maximized().unwrap_or(false),
+                fullscreen: window.is_fullscreen().unwrap_or(false),
+                visible: window.is_visible().unwrap_or(false),
+            },
+        }
+    }
+}
+
+impl From<&Window> for WindowSnapshot {
+    fn from(window: &Window) -> Self {
+        Self::capture(window)
+    }
+}
+
+/// The serializable representation of an [`Event`], tagged by the `"type"` field.
+/// See [`Event`] for the meaning of the variants.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WireEvent {
+    Created,
+    Resized {
+        bounds: Rect,
+    },
+    Moved {
+        bounds: Rect,
+    },
+    MoveStarted,
+    MoveEnded {
+        bounds: Rect,
+    },
+    ResizeStarted,
+    ResizeEnded {
+        bounds: Rect,
+    },
+    Foregrounded,
+    Backgrounded,
+    Focused,
+    Unfocused,
+    Hidden,
+    Showed,
+    Minimized,
+    Restored,
+    Maximized,
+    Unmaximized,
+    EnteredFullscreen,
+    ExitedFullscreen,
+    TitleChanged {
+        old: Option<String>,
+        new: String,
+    },
+    MonitorChanged {
+        from: Monitor,
+        to: Monitor,
+    },
+    /// The windows are captured when the event is converted.
+    StackingChanged {
+        windows: Vec<WindowSnapshot>,
+    },
+    OcclusionChanged {
+        visible_fraction: f64,
+    },
+    DisplayAdded {
+        monitor: Monitor,
+    },
+    DisplayRemoved {
+        monitor: Monitor,
+    },
+    DisplayChanged {
+        from: Monitor,
+        to: Monitor,
+    },
+    /// The ID of the window. See [`WindowSnapshot::id`] for the value.
+    Closed {
+        window_id: u32,
+    },
+    ProcessAttached {
+        pid: u32,
+    },
+    ProcessDetached {
+        pid: u32,
+    },
+    Lifecycle {
+        event: ObserverEvent,
+    },
+    InitialStateCompleted,
+}
+
+impl From<&Event> for WireEvent {
+    fn from(event: &Event) -> Self {
+        match event {
+            Event::Created => Self::Created,
+            Event::Resized { bounds } => Self::Resized { bounds: *bounds },
+            Event::Moved { bounds } => Self::Moved { bounds: *bounds },
+            Event::MoveStarted => Self::MoveStarted,
+            Event::MoveEnded { bounds } => Self::MoveEnded { bounds: *bounds },
+            Event::ResizeStarted => Self::ResizeStarted,
+            Event::ResizeEnded { bounds } => Self::ResizeEnded { bounds: *bounds },
+            Event::Foregrounded => Self::Foregrounded,
+            Event::Backgrounded => Self::Backgrounded,
+            Event::Focused => Self::Focused,
+            Event::Unfocused => Self::Unfocused,
+            Event::Hidden => Self::Hidden,
+            Event::Showed => Self::Showed,
+            Event::Minimized => Self::Minimized,
+            Event::Restored => Self::Restored,
+            Event::Maximized => Self::Maximized,
+            Event::Unmaximized => Self::Unmaximized,
+            Event::EnteredFullscreen => Self::EnteredFullscreen,
+            Event::ExitedFullscreen => Self::ExitedFullscreen,
+            Event::TitleChanged { old, new } => Self::TitleChanged {
+                old: old.clone(),
+                new: new.clone(),
+            },
+            Event::MonitorChanged { from, to } => Self::MonitorChanged {
+                from: (**from).clone(),
+                to: (**to).clone(),
+            },
+            Event::StackingChanged { windows } => Self::StackingChanged {
+                windows: windows.iter().map(WindowSnapshot::capture).collect(),
+            },
+            Event::OcclusionChanged { visible_fraction } => Self::OcclusionChanged {
+                visible_fraction: *visible_fraction,
+            },
+            Event::DisplayAdded { monitor } => Self::DisplayAdded {
+                monitor: (**monitor).clone(),
+            },
+            Event::DisplayRemoved { monitor } => Self::DisplayRemoved {
+                monitor: (**monitor).clone(),
+            },
+            Event::DisplayChanged { from, to } => Self::DisplayChanged {
+                from: (**from).clone(),
+                to: (**to).clone(),
+            },
+            Event::Closed { window_id } => Self::Closed {
+                window_id: window_id.as_u32(),
+            },
+            Event::ProcessAttached { pid } => Self::ProcessAttached { pid: *pid },
+            Event::ProcessDetached { pid } => Self::ProcessDetached { pid: *pid },
+            Event::Lifecycle { event } => Self::Lifecycle { event: *event },
+            Event::InitialStateCompleted => Self::InitialStateCompleted,
+        }
+    }
+}
+
+/// The serializable representation of an [`EventError`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ErrorRecord {
+    /// See [`EventError::kind`].
+    pub kind: ErrorKind,
+    /// See [`EventError::operation`].
+    pub operation: String,
+    /// See [`EventError::window_id`]. See [`WindowSnapshot::id`] for the value.
+    pub window_id: Option<u32>,
+    /// See [`EventError::raw_event`].
+    pub raw_event: Option<String>,
+    /// See [`EventError::suppressed`].
+    pub suppressed: u32,
+    /// The message of the error.
+    pub message: String,
+}
+
+impl From<&EventError> for ErrorRecord {
+    fn from(error: &EventError) -> Self {
+        Self {
+            kind: error.kind(),
+            operation: error.operation().to_owned(),
+            window_id: error.window_id().map(|id| id.as_u32()),
+            raw_event: error.raw_event().map(str::to_owned),
+            suppressed: error.suppressed(),
+            message: error.to_string(),
+        }
+    }
+}
+
+/// The serializable representation of an [`EventResult`], tagged by the `"kind"` field.
+///
+/// The window is captured when the record is created, so its properties may have
+/// changed since the event occurred.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum EventRecord {
+    /// See [`MaybeWindowAvailable::Available`].
+    Available {
+        window: WindowSnapshot,
+        event: WireEvent,
+    },
+    /// See [`MaybeWindowAvailable::NotAvailable`].
+    NotAvailable { event: WireEvent },
+    /// See [`MaybeWindowAvailable::InitialState`].
+    InitialState {
+        window: WindowSnapshot,
+        event: WireEvent,
+    },
+    /// The error of the [`EventResult`].
+    Error { error: ErrorRecord },
+}
+
+impl EventRecord {
+    /// Captures the event and the current properties of its window.
+    pub fn capture(event: &MaybeWindowAvailable) -> Self {
+        match event {
+            MaybeWindowAvailable::Available { window, event } => Self::Available {
+                window: window.into(),
+                event: event.into(),
+            },
+            MaybeWindowAvailable::NotAvailable { event } => Self::NotAvailable {
+                event: event.into(),
+            },
+            MaybeWindowAvailable::InitialState { window, event } => Self::InitialState {
+                window: window.into(),
+                event: event.into(),
+            },
+        }
+    }
+
+    /// Captures the event or the error received from the observer.
+    pub fn capture_result(result: &EventResult) -> Self {
+        match result {
+            Ok(event) => Self::capture(event),
+            Err(error) => Self::Error {
+                error: error.into(),
+            },
+        }
+    }
+
+    /// Returns the event, or `None` if it is an error.
+    pub fn event(&self) -> Option<&WireEvent> {
+        match self {
+            Self::Available { event, .. }
+            | Self::NotAvailable { event }
+            | Self::InitialState { event, .. } => Some(event),
+            Self::Error { .. } => None,
+        }
+    }
+}
+
+impl From<&MaybeWindowAvailable> for EventRecord {
+    fn from(event: &MaybeWindowAvailable) -> Self {
+        Self::capture(event)
+    }
+}
+
+/// Generates the JSON Schema of [`EventRecord`] for the consumers in the other languages.
+#[cfg(feature = "schema")]
+#[cfg_attr(docsrs, doc(cfg(feature = "schema")))]
+pub fn json_schema() -> schemars::Schema {
+    schemars::schema_for!(EventRecord)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::DegradedReason;
+
+    fn bounds() -> Rect {
+        Rect {
+            x: 1.,
+            y: 2.,
+            width: 3.,
+            height: 4.,
+        }
+    }
+
+    fn round_trip(record: &EventRecord) -> EventRecord {
+        serde_json::from_str(&serde_json::to_string(record).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn records_survive_a_round_trip() {
+        let records = [
+            EventRecord::Available {
+                window: WindowSnapshot {
+                    id: Some(1),
+                    pid: Some(2),
+                    title: Some("title".to_owned()),
+                    bounds: Some(bounds()),
+                    focused: true,
+                    state: WindowState {
+                        visible: true,
+                        ..Default::default()
+                    },
+                },
+                event: WireEvent::TitleChanged {
+                    old: None,
+                    new: "title".to_owned(),
+                },
+            },
+            EventRecord::InitialState {
+                window: WindowSnapshot::default(),
+                event: WireEvent::Created,
+            },
+            EventRecord::NotAvailable {
+                event: WireEvent::Closed { window_id: 1 },
+            },
+            EventRecord::NotAvailable {
+                event: WireEvent::Lifecycle {
+                    event: ObserverEvent::Degraded {
+                        reason: DegradedReason::BackendEnded,
+                    },
+                },
+            },
+            EventRecord::Error {
+                error: ErrorRecord {
+                    kind: ErrorKind::Transient,
+                    operation: "interpreting an event".to_owned(),
+                    window_id: None,
+                    raw_event: Some("Destroy".to_owned()),
+                    suppressed: 3,
+                    message: "message".to_owned(),
+                },
+            },
+        ];
+
+        for record in records {
+            assert_eq!(round_trip(&record), record);
+        }
+    }
+
+    #[test]
+    fn tags_are_snake_case() {
+        let record = EventRecord::NotAvailable {
+            event: WireEvent::MoveEnded { bounds: bounds() },
+        };
+
+        assert_eq!(
+            serde_json::to_value(&record).unwrap(),
+            json!({
+                "kind": "not_available",
+                "event": {
+                    "type": "move_ended",
+                    "bounds": { "x": 1.0, "y": 2.0, "width": 3.0, "height": 4.0 },
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn nested_tags_are_snake_case() {
+        let record = EventRecord::NotAvailable {
+            event: WireEvent::Lifecycle {
+                event: ObserverEvent::Degraded {
+                    reason: DegradedReason::BackendEnded,
+                },
+            },
+        };
+
+        assert_eq!(
+            serde_json::to_value(&record).unwrap(),
+            json!({
+                "kind": "not_available",
+                "event": {
+                    "type": "lifecycle",
+                    "event": { "type": "degraded", "reason": "backend_ended" },
+                },
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(WireEvent::InitialStateCompleted).unwrap(),
+            json!({ "type": "initial_state_completed" })
+        );
+    }
+
+    #[test]
+    fn error_kinds_are_snake_case() {
+        assert_eq!(
+            serde_json::to_value(ErrorKind::WindowGone).unwrap(),
+            json!("window_gone")
+        );
+        assert_eq!(
+            serde_json::to_value(ErrorKind::PermissionDenied).unwrap(),
+            json!("permission_denied")
+        );
+    }
+
+    #[cfg(feature = "schema")]
+    #[test]
+    fn json_schema_describes_the_records() {
+        let schema = serde_json::to_value(json_schema()).unwrap();
+
+        assert_eq!(schema["title"], "EventRecord");
+        let schema = schema.to_string();
+        for tag in [
+            "not_available",
+            "initial_state",
+            "move_ended",
+            "window_gone",
+        ] {
+            assert!(schema.contains(&format!("\"{tag}\"")), "{tag} is missing");
+        }
+    }
+}